@@ -14,55 +14,82 @@ fn c() -> Criterion {
 fn git_hash() -> String {
     use std::process::Command;
     let output = Command::new("git")
-        .args(&["rev-parse", "--short", "HEAD"])
+        .args(["rev-parse", "--short", "HEAD"])
         .output()
         .unwrap();
     String::from(String::from_utf8(output.stdout).unwrap().trim())
 }
 
 mod fid {
-    use criterion::{BatchSize, Criterion};
-    use fid_rs::Fid;
+    use criterion::{BatchSize, BenchmarkId, Criterion};
+    use fid_rs::{BitOrder, Fid};
 
     const NS: [u64; 5] = [1 << 16, 1 << 17, 1 << 18, 1 << 19, 1 << 20];
+    const LARGE_NS: [u64; 5] = [1 << 24, 1 << 26, 1 << 28, 1 << 30, 1 << 32];
 
     pub fn from_str_benchmark(_: &mut Criterion) {
-        super::c().bench_function_over_inputs(
-            &format!(
-                "[{}] Fid::from(\"00...(repeated N-times)\")",
-                super::git_hash()
-            ),
-            |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Fid::from(\"00...(repeated N-times)\")",
+            super::git_hash()
+        ));
+        for &n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, &n| {
                 b.iter_batched(
-                    || String::from_utf8(vec!['0' as u8; n as usize]).unwrap(),
+                    || String::from_utf8(vec![b'0'; n as usize]).unwrap(),
                     |s| Fid::from(s.as_str()),
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 
     pub fn from_slice_benchmark(_: &mut Criterion) {
-        super::c().bench_function_over_inputs(
-            &format!("[{}] Fid::from(&[false; N])", super::git_hash()),
-            |b, &&n| {
+        let mut c = super::c();
+        let mut group =
+            c.benchmark_group(format!("[{}] Fid::from(&[false; N])", super::git_hash()));
+        for &n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, &n| {
                 b.iter_batched(
                     || vec![false; n as usize],
                     |v| Fid::from(&v[..]),
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
+    }
+
+    pub fn from_raw_parts_benchmark(_: &mut Criterion) {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Fid::from_raw_parts(N bits)",
+            super::git_hash()
+        ));
+        for &n in LARGE_NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, &n| {
+                b.iter_batched(
+                    || vec![0b0101_1010u8; (n / 8) as usize],
+                    |bytes| Fid::from_raw_parts(bytes, n, BitOrder::MsbFirst),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+        group.finish();
     }
 
     pub fn rank_benchmark(_: &mut Criterion) {
         let times = 1_000_000;
 
-        super::c().bench_function_over_inputs(
-            &format!("[{}] Fid::rank(N) {} times", super::git_hash(), times),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Fid::rank(N) {} times",
+            super::git_hash(),
+            times
+        ));
+        for &n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), &n, move |b, &n| {
                 b.iter_batched(
                     || {
                         let v = vec![false; n as usize];
@@ -77,17 +104,22 @@ mod fid {
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 
-    pub fn select_benchmark(_: &mut Criterion) {
+    pub fn select1_benchmark(_: &mut Criterion) {
         let times = 1_000;
 
-        super::c().bench_function_over_inputs(
-            &format!("[{}] Fid::select(N) {} times", super::git_hash(), times),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Fid::select1(N) {} times",
+            super::git_hash(),
+            times
+        ));
+        for &n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), &n, move |b, &n| {
                 b.iter_batched(
                     || {
                         let v = vec![true; n as usize];
@@ -97,22 +129,27 @@ mod fid {
                         // iter_batched() does not properly time `routine` time when `setup` time is far longer than `routine` time.
                         // Tested function takes too short compared to build(). So loop many times.
                         for _ in 0..times {
-                            assert_eq!(fid.select(n - 1), Some(n - 2));
+                            assert_eq!(fid.select1(n - 2), Some(n - 2));
                         }
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 
     pub fn rank0_benchmark(_: &mut Criterion) {
         let times = 1_000_000;
 
-        super::c().bench_function_over_inputs(
-            &format!("[{}] Fid::rank0(N) {} times", super::git_hash(), times),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Fid::rank0(N) {} times",
+            super::git_hash(),
+            times
+        ));
+        for &n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), &n, move |b, &n| {
                 b.iter_batched(
                     || {
                         let v = vec![false; n as usize];
@@ -127,17 +164,22 @@ mod fid {
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 
     pub fn select0_benchmark(_: &mut Criterion) {
         let times = 1_000;
 
-        super::c().bench_function_over_inputs(
-            &format!("[{}] Fid::select0(N) {} times", super::git_hash(), times),
-            move |b, &&n| {
+        let mut c = super::c();
+        let mut group = c.benchmark_group(format!(
+            "[{}] Fid::select0(N) {} times",
+            super::git_hash(),
+            times
+        ));
+        for &n in NS.iter() {
+            group.bench_with_input(BenchmarkId::from_parameter(n), &n, move |b, &n| {
                 b.iter_batched(
                     || {
                         let v = vec![false; n as usize];
@@ -147,14 +189,14 @@ mod fid {
                         // iter_batched() does not properly time `routine` time when `setup` time is far longer than `routine` time.
                         // Tested function takes too short compared to build(). So loop many times.
                         for _ in 0..times {
-                            assert_eq!(fid.select0(n - 1), Some(n - 2));
+                            assert_eq!(fid.select0(n - 2), Some(n - 2));
                         }
                     },
                     BatchSize::SmallInput,
                 )
-            },
-            &NS,
-        );
+            });
+        }
+        group.finish();
     }
 }
 
@@ -162,8 +204,9 @@ criterion_group!(
     benches,
     fid::from_str_benchmark,
     fid::from_slice_benchmark,
+    fid::from_raw_parts_benchmark,
     fid::rank_benchmark,
-    fid::select_benchmark,
+    fid::select1_benchmark,
     fid::rank0_benchmark,
     fid::select0_benchmark,
 );
@@ -0,0 +1,45 @@
+//! Builds a `Fid` from arbitrary bits and compares every query with a naive scan.
+
+#![no_main]
+
+use fid_rs::Fid;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // 1st byte selects the constructor. Each following byte is a bit.
+    let Some((&kind, data)) = data.split_first() else {
+        return;
+    };
+    if data.is_empty() {
+        return;
+    }
+    let bits: Vec<bool> = data.iter().map(|&b| b & 1 == 1).collect();
+    let fid = if kind & 1 == 0 {
+        Fid::from(&bits[..])
+    } else {
+        let s: String = bits.iter().map(|&b| if b { '1' } else { '0' }).collect();
+        Fid::from(s.as_str())
+    };
+
+    assert_eq!(fid.len(), bits.len() as u64);
+    let mut rank = 0;
+    let mut ones = Vec::new();
+    let mut zeros = Vec::new();
+    for (i, &bit) in bits.iter().enumerate() {
+        let i = i as u64;
+        if bit {
+            rank += 1;
+            ones.push(i);
+        } else {
+            zeros.push(i);
+        }
+        assert_eq!(fid[i], bit);
+        assert_eq!(fid.rank(i), rank);
+        assert_eq!(fid.rank0(i), i + 1 - rank);
+    }
+    for k in 0..=fid.len() {
+        assert_eq!(fid.select1(k), ones.get(k as usize).copied());
+        assert_eq!(fid.select0(k), zeros.get(k as usize).copied());
+    }
+    assert!(fid.iter().eq(bits.iter().copied()));
+});
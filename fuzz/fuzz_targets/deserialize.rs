@@ -0,0 +1,35 @@
+//! Deserializes arbitrary bytes into a `Fid` and runs queries on it.
+//!
+//! Deserialization itself must not panic nor allocate unboundedly. A `Fid` which deserialized
+//! successfully must pass `Fid::validate()`, and queries on it must not panic.
+
+#![no_main]
+
+use bincode::Options;
+use fid_rs::Fid;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let options = bincode::options()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(1 << 20);
+    let Ok(fid) = options.deserialize::<Fid>(data) else {
+        return;
+    };
+    assert_eq!(fid.validate(), Ok(()));
+
+    let n = fid.len();
+    for i in [0, n / 2, n.saturating_sub(1)] {
+        if i < n {
+            let _ = fid[i];
+            let _ = fid.rank(i);
+            let _ = fid.rank0(i);
+        }
+    }
+    for k in [0, 1, n / 2, n] {
+        let _ = fid.select1(k);
+        let _ = fid.select0(k);
+    }
+    let _ = fid.iter().count();
+});
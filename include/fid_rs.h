@@ -0,0 +1,168 @@
+#ifndef FID_RS_H
+#define FID_RS_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of FFI functions.
+ */
+typedef enum FidStatus {
+  /**
+   * Succeeded.
+   */
+  FID_STATUS_OK = 0,
+  /**
+   * A pointer argument is NULL.
+   */
+  FID_STATUS_NULL_POINTER = 1,
+  /**
+   * An argument is invalid (e.g. zero bit length, or byte length inconsistent with bit length).
+   */
+  FID_STATUS_INVALID_ARGUMENT = 2,
+  /**
+   * An index is out of the `Fid`.
+   */
+  FID_STATUS_OUT_OF_RANGE = 3,
+  /**
+   * `fid_select1()`, `fid_select0()` or `fid_select()` found no answer.
+   */
+  FID_STATUS_NOT_FOUND = 4,
+  /**
+   * Unexpected panic inside fid-rs.
+   */
+  FID_STATUS_PANIC = 5,
+} FidStatus;
+
+/**
+ * Opaque handle of a `Fid`.
+ *
+ * Created by `fid_new_from_bytes()` or `fid_deserialize()`, and must be released by `fid_free()`.
+ */
+typedef struct FidHandle FidHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Builds a `Fid` from `bytes_len` bytes of packed bits (MSB-first) and its bit length.
+ *
+ * `bytes_len` must be _ceil(`bit_len` / 8)_. `*out` is set to a new handle on success.
+ *
+ * # Safety
+ * `bytes` must point to `bytes_len` readable bytes, and `out` must be a valid pointer.
+ */
+enum FidStatus fid_new_from_bytes(const uint8_t *bytes,
+                                  size_t bytes_len,
+                                  uint64_t bit_len,
+                                  struct FidHandle **out);
+
+/**
+ * Releases a handle. Does nothing for NULL.
+ *
+ * # Safety
+ * `handle` must be NULL or a handle not yet released.
+ */
+void fid_free(struct FidHandle *handle);
+
+/**
+ * Sets bit length of the `Fid` to `*out`.
+ *
+ * # Safety
+ * `handle` must be a live handle and `out` must be a valid pointer.
+ */
+enum FidStatus fid_len(const struct FidHandle *handle, uint64_t *out);
+
+/**
+ * Sets `i`-th bit of the `Fid` to `*out`.
+ *
+ * # Safety
+ * `handle` must be a live handle and `out` must be a valid pointer.
+ */
+enum FidStatus fid_get(const struct FidHandle *handle, uint64_t i, bool *out);
+
+/**
+ * Sets `Fid::rank(i)` to `*out`.
+ *
+ * # Safety
+ * `handle` must be a live handle and `out` must be a valid pointer.
+ */
+enum FidStatus fid_rank(const struct FidHandle *handle, uint64_t i, uint64_t *out);
+
+/**
+ * Sets `Fid::rank0(i)` to `*out`.
+ *
+ * # Safety
+ * `handle` must be a live handle and `out` must be a valid pointer.
+ */
+enum FidStatus fid_rank0(const struct FidHandle *handle, uint64_t i, uint64_t *out);
+
+/**
+ * Sets `Fid::select1(k)` to `*out`. Returns `NotFound` when it is `None`.
+ *
+ * # Safety
+ * `handle` must be a live handle and `out` must be a valid pointer.
+ */
+enum FidStatus fid_select1(const struct FidHandle *handle, uint64_t k, uint64_t *out);
+
+/**
+ * Sets `Fid::select(num)` to `*out`. Returns `NotFound` when it is `None`, and `OutOfRange` when _`num` > length_.
+ *
+ * Deprecated: use `fid_select1()`, which takes 0-origin `k`. `fid_select(0)` is always _0_,
+ * and `fid_select(num)` equals `fid_select1(num - 1)` for _`num` > 0_.
+ *
+ * # Safety
+ * `handle` must be a live handle and `out` must be a valid pointer.
+ */
+enum FidStatus fid_select(const struct FidHandle *handle,
+                          uint64_t num,
+                          uint64_t *out);
+
+/**
+ * Sets `Fid::select0(k)` to `*out`. Returns `NotFound` when it is `None`.
+ *
+ * # Safety
+ * `handle` must be a live handle and `out` must be a valid pointer.
+ */
+enum FidStatus fid_select0(const struct FidHandle *handle, uint64_t k, uint64_t *out);
+
+/**
+ * Serializes the `Fid` into a newly allocated buffer.
+ *
+ * `*out_buf` and `*out_len` are set to the buffer and its length on success.
+ * The buffer must be released by `fid_bytes_free()`.
+ *
+ * # Safety
+ * `handle` must be a live handle, and `out_buf` and `out_len` must be valid pointers.
+ */
+enum FidStatus fid_serialize(const struct FidHandle *handle, uint8_t **out_buf, size_t *out_len);
+
+/**
+ * Releases a buffer returned by `fid_serialize()`. Does nothing for NULL.
+ *
+ * # Safety
+ * `buf` and `len` must be the ones returned by `fid_serialize()` and not yet released.
+ */
+void fid_bytes_free(uint8_t *buf, size_t len);
+
+/**
+ * Builds a `Fid` from a buffer written by `fid_serialize()`.
+ *
+ * `*out` is set to a new handle on success.
+ *
+ * # Safety
+ * `buf` must point to `len` readable bytes, and `out` must be a valid pointer.
+ */
+enum FidStatus fid_deserialize(const uint8_t *buf, size_t len, struct FidHandle **out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FID_RS_H */
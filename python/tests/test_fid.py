@@ -0,0 +1,70 @@
+# Run with `maturin develop && pytest python/tests`.
+import pickle
+
+import numpy as np
+import pytest
+
+from fid_rs import Fid
+
+
+def test_from_bool_array():
+    fid = Fid(np.array([False, True, False, False, True]))
+    assert len(fid) == 5
+    assert fid.rank(4) == 2
+    assert fid.rank0(4) == 3
+    assert fid.select1(1) == 4
+    assert fid.select1(2) is None
+    assert fid.select0(2) == 3
+
+
+def test_from_packed_uint8_array_and_bytes():
+    bits = np.array([0, 1, 0, 0, 1, 1, 0, 1, 1, 1], dtype=bool)
+    packed = np.packbits(bits)
+    for fid in (Fid(packed, len(bits)), Fid(packed.tobytes(), len(bits))):
+        assert len(fid) == len(bits)
+        assert list(fid[np.arange(len(bits))]) == list(bits)
+
+
+def test_vectorized_queries():
+    bits = np.random.default_rng(0).random(1000) < 0.3
+    fid = Fid(bits)
+    idx = np.arange(len(bits))
+    np.testing.assert_array_equal(fid.rank(idx), np.cumsum(bits))
+    np.testing.assert_array_equal(fid.rank0(idx), np.cumsum(~bits))
+    np.testing.assert_array_equal(fid[idx], bits)
+
+    ks = np.arange(bits.sum() + 1)
+    expected = list(np.flatnonzero(bits)) + [-1]
+    np.testing.assert_array_equal(fid.select1(ks), expected)
+
+
+def test_deprecated_select():
+    fid = Fid(np.array([False, True, False, False, True]))
+    with pytest.deprecated_call():
+        assert fid.select(0) == 0
+    with pytest.deprecated_call():
+        assert fid.select(2) == 4
+    with pytest.deprecated_call():
+        assert fid.select(3) is None
+    with pytest.deprecated_call():
+        np.testing.assert_array_equal(fid.select(np.array([0, 1, 2, 3])), [0, 1, 4, -1])
+    with pytest.raises(IndexError), pytest.warns(DeprecationWarning):
+        fid.select(6)
+
+
+def test_out_of_range():
+    fid = Fid(np.array([True, False]))
+    with pytest.raises(IndexError):
+        fid[2]
+    with pytest.raises(IndexError):
+        fid.rank(np.array([0, 2]))
+    with pytest.raises(IndexError):
+        fid.select1(-1)
+    with pytest.raises(ValueError):
+        Fid(b"\x00\x00", 3)
+
+
+def test_pickle():
+    fid = Fid(np.array([True, False, True]))
+    restored = pickle.loads(pickle.dumps(fid))
+    assert list(restored[np.arange(3)]) == [True, False, True]
@@ -0,0 +1,240 @@
+//! Command-line tool to build, inspect and query serialized FID files.
+//!
+//! ```text
+//! $ echo 0100_1 | fid build --format bits - a.fid
+//! $ fid stats a.fid
+//! $ echo 4 | fid query rank a.fid
+//! 2
+//! ```
+//!
+//! FID files are `Fid`s serialized by `bincode`.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use fid_rs::{BitOrder, Fid, ReadFormat};
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "fid", version, about = "Build, inspect and query FID files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Builds a FID file from INPUT.
+    Build {
+        /// Format of INPUT.
+        #[arg(long, value_enum, default_value_t = InputFormat::Bits)]
+        format: InputFormat,
+
+        /// Bit length. Required for `positions`. Defaults to 8 * file size for `bytes`.
+        #[arg(long)]
+        len: Option<u64>,
+
+        /// Input file, or `-` for stdin.
+        input: PathBuf,
+
+        /// Output FID file.
+        output: PathBuf,
+    },
+
+    /// Prints length, popcount, density and space breakdown of a FID file.
+    Stats {
+        /// FID file.
+        fid: PathBuf,
+    },
+
+    /// Answers queries for positions (or 0-origin `k` for select1/select0) read from stdin, one per line.
+    Query {
+        /// Query to answer.
+        #[arg(value_enum)]
+        op: QueryOp,
+
+        /// FID file.
+        fid: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum InputFormat {
+    /// Text of '0' and '1'. '_' and whitespaces are ignored.
+    Bits,
+    /// Raw packed bytes (MSB-first).
+    Bytes,
+    /// Newline-separated positions of '1'.
+    Positions,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum QueryOp {
+    Rank,
+    Rank0,
+    Select1,
+    Select0,
+    Get,
+    /// Deprecated: 1-origin `num` in [0, length] like `Fid::select()`. Use `select1` with `num - 1`.
+    Select,
+}
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+fn open_input(path: &Path) -> Result<Box<dyn Read>> {
+    if path == Path::new("-") {
+        Ok(Box::new(io::stdin()))
+    } else {
+        Ok(Box::new(File::open(path)?))
+    }
+}
+
+fn read_fid(format: InputFormat, len: Option<u64>, mut input: impl Read) -> Result<Fid> {
+    let fid = match format {
+        InputFormat::Bits => {
+            let fid = Fid::from_reader(input, ReadFormat::Text)?;
+            if let Some(len) = len {
+                if len != fid.len() {
+                    return Err(format!("--len {} differs from {} bits", len, fid.len()).into());
+                }
+            }
+            fid
+        }
+        InputFormat::Bytes => match len {
+            Some(bit_len) => Fid::from_reader(
+                input,
+                ReadFormat::Bytes {
+                    bit_len,
+                    bit_order: BitOrder::MsbFirst,
+                },
+            )?,
+            None => {
+                let mut bytes = Vec::new();
+                input.read_to_end(&mut bytes)?;
+                if bytes.is_empty() {
+                    return Err("input has no bits".into());
+                }
+                let bit_len = bytes.len() as u64 * 8;
+                Fid::from_raw_parts(bytes, bit_len, BitOrder::MsbFirst)
+            }
+        },
+        InputFormat::Positions => {
+            let len = len.ok_or("--len is required for `positions`")?;
+            Fid::from_reader(input, ReadFormat::Positions { len })?
+        }
+    };
+    Ok(fid)
+}
+
+fn load(path: &Path) -> Result<Fid> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(bincode::deserialize_from(reader)?)
+}
+
+fn build(format: InputFormat, len: Option<u64>, input: &Path, output: &Path) -> Result<()> {
+    let fid = read_fid(format, len, open_input(input)?)?;
+    let mut writer = BufWriter::new(File::create(output)?);
+    bincode::serialize_into(&mut writer, &fid)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn stats(path: &Path, out: &mut impl Write) -> Result<()> {
+    let fid = load(path)?;
+    let n = fid.len();
+    let popcount = fid.rank(n - 1);
+    let report = fid.space_report();
+
+    writeln!(out, "length:          {}", n)?;
+    writeln!(out, "popcount:        {}", popcount)?;
+    writeln!(out, "density:         {:.6}", popcount as f64 / n as f64)?;
+    writeln!(out, "raw bits:        {} bytes", report.raw_bits)?;
+    writeln!(out, "chunk directory: {} bytes", report.chunk_directory)?;
+    writeln!(out, "block directory: {} bytes", report.block_directory)?;
+    writeln!(out, "popcount table:  {} bytes", report.popcount_table)?;
+    writeln!(out, "select samples:  {} bytes", report.select_samples)?;
+    writeln!(out, "total:           {} bytes", report.total())?;
+    writeln!(
+        out,
+        "overhead:        {:.4} bits per bit",
+        report.overhead_bits_per_bit()
+    )?;
+    Ok(())
+}
+
+fn write_position(out: &mut impl Write, pos: Option<u64>) -> io::Result<()> {
+    match pos {
+        Some(pos) => writeln!(out, "{}", pos),
+        None => writeln!(out, "none"),
+    }
+}
+
+fn query(op: QueryOp, path: &Path, input: impl BufRead, out: &mut impl Write) -> Result<()> {
+    let fid = load(path)?;
+    let n = fid.len();
+
+    for (lineno, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let x: u64 = line
+            .parse()
+            .map_err(|e| format!("line {}: {:?}: {}", lineno + 1, line, e))?;
+
+        let in_range = match op {
+            QueryOp::Select1 | QueryOp::Select0 => true,
+            QueryOp::Select => x <= n,
+            _ => x < n,
+        };
+        if !in_range {
+            return Err(format!(
+                "line {}: {} is out of range (length = {})",
+                lineno + 1,
+                x,
+                n
+            )
+            .into());
+        }
+
+        match op {
+            QueryOp::Rank => writeln!(out, "{}", fid.rank(x))?,
+            QueryOp::Rank0 => writeln!(out, "{}", fid.rank0(x))?,
+            QueryOp::Get => writeln!(out, "{}", u8::from(fid[x]))?,
+            QueryOp::Select1 => write_position(out, fid.select1(x))?,
+            QueryOp::Select0 => write_position(out, fid.select0(x))?,
+            #[allow(deprecated)]
+            QueryOp::Select => write_position(out, fid.select(x))?,
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let result = match cli.command {
+        Command::Build {
+            format,
+            len,
+            input,
+            output,
+        } => build(format, len, &input, &output),
+        Command::Stats { fid } => stats(&fid, &mut out),
+        Command::Query { op, fid } => query(op, &fid, io::stdin().lock(), &mut out),
+    }
+    .and_then(|()| Ok(out.flush()?));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("fid: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,463 @@
+//! C ABI for building and querying a [Fid](../fid/struct.Fid.html).
+//!
+//! Enabled by `ffi` feature. A `Fid` is passed to C as an opaque `FidHandle *`,
+//! and every function returns a [FidStatus](enum.FidStatus.html) instead of panicking across the boundary.
+//!
+//! Build a C library and its header with:
+//!
+//! ```text
+//! $ cargo rustc --release --features ffi --crate-type cdylib    # or staticlib
+//! $ cbindgen --config cbindgen.toml --output include/fid_rs.h
+//! ```
+//!
+//! # Serialized format
+//! `fid_serialize()` writes the bit length as 8-byte little endian integer,
+//! followed by the packed bits (MSB-first) of the `Fid`.
+
+use crate::{BitOrder, Fid};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice};
+
+/// Opaque handle of a `Fid`.
+///
+/// Created by `fid_new_from_bytes()` or `fid_deserialize()`, and must be released by `fid_free()`.
+pub struct FidHandle {
+    fid: Fid,
+}
+
+/// Result of FFI functions.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FidStatus {
+    /// Succeeded.
+    Ok = 0,
+    /// A pointer argument is NULL.
+    NullPointer = 1,
+    /// An argument is invalid (e.g. zero bit length, or byte length inconsistent with bit length).
+    InvalidArgument = 2,
+    /// An index is out of the `Fid`.
+    OutOfRange = 3,
+    /// `fid_select1()`, `fid_select0()` or `fid_select()` found no answer.
+    NotFound = 4,
+    /// Unexpected panic inside fid-rs.
+    Panic = 5,
+}
+
+fn catch<F: FnOnce() -> FidStatus>(f: F) -> FidStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(FidStatus::Panic)
+}
+
+fn new_fid(bytes: &[u8], bit_len: u64) -> Result<Fid, FidStatus> {
+    if bit_len == 0 || bit_len.div_ceil(8) != bytes.len() as u64 {
+        return Err(FidStatus::InvalidArgument);
+    }
+    Ok(Fid::from_raw_parts(
+        bytes.to_vec(),
+        bit_len,
+        BitOrder::MsbFirst,
+    ))
+}
+
+/// Builds a `Fid` from `bytes_len` bytes of packed bits (MSB-first) and its bit length.
+///
+/// `bytes_len` must be _ceil(`bit_len` / 8)_. `*out` is set to a new handle on success.
+///
+/// # Safety
+/// `bytes` must point to `bytes_len` readable bytes, and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn fid_new_from_bytes(
+    bytes: *const u8,
+    bytes_len: usize,
+    bit_len: u64,
+    out: *mut *mut FidHandle,
+) -> FidStatus {
+    if bytes.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    catch(|| {
+        let bytes = slice::from_raw_parts(bytes, bytes_len);
+        match new_fid(bytes, bit_len) {
+            Ok(fid) => {
+                *out = Box::into_raw(Box::new(FidHandle { fid }));
+                FidStatus::Ok
+            }
+            Err(status) => status,
+        }
+    })
+}
+
+/// Releases a handle. Does nothing for NULL.
+///
+/// # Safety
+/// `handle` must be NULL or a handle not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn fid_free(handle: *mut FidHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Sets bit length of the `Fid` to `*out`.
+///
+/// # Safety
+/// `handle` must be a live handle and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn fid_len(handle: *const FidHandle, out: *mut u64) -> FidStatus {
+    if handle.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    *out = (*handle).fid.len();
+    FidStatus::Ok
+}
+
+/// Sets `i`-th bit of the `Fid` to `*out`.
+///
+/// # Safety
+/// `handle` must be a live handle and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn fid_get(handle: *const FidHandle, i: u64, out: *mut bool) -> FidStatus {
+    if handle.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    let fid = &(*handle).fid;
+    if i >= fid.len() {
+        return FidStatus::OutOfRange;
+    }
+    catch(|| {
+        *out = fid[i];
+        FidStatus::Ok
+    })
+}
+
+/// Sets `Fid::rank(i)` to `*out`.
+///
+/// # Safety
+/// `handle` must be a live handle and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn fid_rank(handle: *const FidHandle, i: u64, out: *mut u64) -> FidStatus {
+    if handle.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    let fid = &(*handle).fid;
+    if i >= fid.len() {
+        return FidStatus::OutOfRange;
+    }
+    catch(|| {
+        *out = fid.rank(i);
+        FidStatus::Ok
+    })
+}
+
+/// Sets `Fid::rank0(i)` to `*out`.
+///
+/// # Safety
+/// `handle` must be a live handle and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn fid_rank0(handle: *const FidHandle, i: u64, out: *mut u64) -> FidStatus {
+    if handle.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    let fid = &(*handle).fid;
+    if i >= fid.len() {
+        return FidStatus::OutOfRange;
+    }
+    catch(|| {
+        *out = fid.rank0(i);
+        FidStatus::Ok
+    })
+}
+
+/// Sets `Fid::select1(k)` to `*out`. Returns `NotFound` when it is `None`.
+///
+/// # Safety
+/// `handle` must be a live handle and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn fid_select1(handle: *const FidHandle, k: u64, out: *mut u64) -> FidStatus {
+    if handle.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    let fid = &(*handle).fid;
+    catch(|| match fid.select1(k) {
+        Some(i) => {
+            *out = i;
+            FidStatus::Ok
+        }
+        None => FidStatus::NotFound,
+    })
+}
+
+/// Sets `Fid::select(num)` to `*out`. Returns `NotFound` when it is `None`, and `OutOfRange` when _`num` > length_.
+///
+/// Deprecated: use `fid_select1()`, which takes 0-origin `k`. `fid_select(0)` is always _0_,
+/// and `fid_select(num)` equals `fid_select1(num - 1)` for _`num` > 0_.
+///
+/// # Safety
+/// `handle` must be a live handle and `out` must be a valid pointer.
+#[no_mangle]
+#[deprecated(
+    since = "0.3.0",
+    note = "use `fid_select1()`, which takes 0-origin `k`"
+)]
+pub unsafe extern "C" fn fid_select(
+    handle: *const FidHandle,
+    num: u64,
+    out: *mut u64,
+) -> FidStatus {
+    if handle.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    let fid = &(*handle).fid;
+    if num > fid.len() {
+        return FidStatus::OutOfRange;
+    }
+    #[allow(deprecated)]
+    catch(|| match fid.select(num) {
+        Some(i) => {
+            *out = i;
+            FidStatus::Ok
+        }
+        None => FidStatus::NotFound,
+    })
+}
+
+/// Sets `Fid::select0(k)` to `*out`. Returns `NotFound` when it is `None`.
+///
+/// # Safety
+/// `handle` must be a live handle and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn fid_select0(handle: *const FidHandle, k: u64, out: *mut u64) -> FidStatus {
+    if handle.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    let fid = &(*handle).fid;
+    catch(|| match fid.select0(k) {
+        Some(i) => {
+            *out = i;
+            FidStatus::Ok
+        }
+        None => FidStatus::NotFound,
+    })
+}
+
+/// Serializes the `Fid` into a newly allocated buffer.
+///
+/// `*out_buf` and `*out_len` are set to the buffer and its length on success.
+/// The buffer must be released by `fid_bytes_free()`.
+///
+/// # Safety
+/// `handle` must be a live handle, and `out_buf` and `out_len` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn fid_serialize(
+    handle: *const FidHandle,
+    out_buf: *mut *mut u8,
+    out_len: *mut usize,
+) -> FidStatus {
+    if handle.is_null() || out_buf.is_null() || out_len.is_null() {
+        return FidStatus::NullPointer;
+    }
+    catch(|| {
+        let buf = (*handle).fid.to_length_prefixed_bytes().into_boxed_slice();
+        *out_len = buf.len();
+        *out_buf = Box::into_raw(buf) as *mut u8;
+        FidStatus::Ok
+    })
+}
+
+/// Releases a buffer returned by `fid_serialize()`. Does nothing for NULL.
+///
+/// # Safety
+/// `buf` and `len` must be the ones returned by `fid_serialize()` and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn fid_bytes_free(buf: *mut u8, len: usize) {
+    if !buf.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buf, len)));
+    }
+}
+
+/// Builds a `Fid` from a buffer written by `fid_serialize()`.
+///
+/// `*out` is set to a new handle on success.
+///
+/// # Safety
+/// `buf` must point to `len` readable bytes, and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn fid_deserialize(
+    buf: *const u8,
+    len: usize,
+    out: *mut *mut FidHandle,
+) -> FidStatus {
+    if buf.is_null() || out.is_null() {
+        return FidStatus::NullPointer;
+    }
+    catch(|| {
+        let buf = slice::from_raw_parts(buf, len);
+        match Fid::from_length_prefixed_bytes(buf) {
+            Some(fid) => {
+                *out = Box::into_raw(Box::new(FidHandle { fid }));
+                FidStatus::Ok
+            }
+            None => FidStatus::InvalidArgument,
+        }
+    })
+}
+
+#[cfg(test)]
+mod fid_new_from_bytes_success_tests {
+    use super::*;
+
+    #[test]
+    fn query() {
+        unsafe {
+            let bytes = [0b0100_1000u8];
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                fid_new_from_bytes(bytes.as_ptr(), bytes.len(), 5, &mut handle),
+                FidStatus::Ok
+            );
+
+            let mut len = 0;
+            assert_eq!(fid_len(handle, &mut len), FidStatus::Ok);
+            assert_eq!(len, 5);
+
+            let mut bit = false;
+            assert_eq!(fid_get(handle, 1, &mut bit), FidStatus::Ok);
+            assert!(bit);
+            assert_eq!(fid_get(handle, 5, &mut bit), FidStatus::OutOfRange);
+
+            let mut n = 0;
+            assert_eq!(fid_rank(handle, 4, &mut n), FidStatus::Ok);
+            assert_eq!(n, 2);
+            assert_eq!(fid_rank0(handle, 4, &mut n), FidStatus::Ok);
+            assert_eq!(n, 3);
+            assert_eq!(fid_rank(handle, 5, &mut n), FidStatus::OutOfRange);
+
+            assert_eq!(fid_select1(handle, 1, &mut n), FidStatus::Ok);
+            assert_eq!(n, 4);
+            assert_eq!(fid_select1(handle, 2, &mut n), FidStatus::NotFound);
+            assert_eq!(fid_select0(handle, 2, &mut n), FidStatus::Ok);
+            assert_eq!(n, 3);
+            assert_eq!(fid_select0(handle, 6, &mut n), FidStatus::NotFound);
+
+            #[allow(deprecated)]
+            {
+                assert_eq!(fid_select(handle, 0, &mut n), FidStatus::Ok);
+                assert_eq!(n, 0);
+                assert_eq!(fid_select(handle, 2, &mut n), FidStatus::Ok);
+                assert_eq!(n, 4);
+                assert_eq!(fid_select(handle, 3, &mut n), FidStatus::NotFound);
+                assert_eq!(fid_select(handle, 6, &mut n), FidStatus::OutOfRange);
+            }
+
+            fid_free(handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod fid_new_from_bytes_failure_tests {
+    use super::*;
+
+    #[test]
+    fn null_pointer() {
+        unsafe {
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                fid_new_from_bytes(ptr::null(), 1, 5, &mut handle),
+                FidStatus::NullPointer
+            );
+            assert!(handle.is_null());
+        }
+    }
+
+    #[test]
+    fn zero_bit_len() {
+        unsafe {
+            let bytes = [0u8];
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                fid_new_from_bytes(bytes.as_ptr(), 0, 0, &mut handle),
+                FidStatus::InvalidArgument
+            );
+        }
+    }
+
+    #[test]
+    fn inconsistent_bytes_len() {
+        unsafe {
+            let bytes = [0u8, 0u8];
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                fid_new_from_bytes(bytes.as_ptr(), bytes.len(), 8, &mut handle),
+                FidStatus::InvalidArgument
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod fid_serialize_success_tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        unsafe {
+            // Unused bits in the last byte must not survive.
+            let bytes = [0b1010_1010u8, 0b1111_1111];
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                fid_new_from_bytes(bytes.as_ptr(), bytes.len(), 11, &mut handle),
+                FidStatus::Ok
+            );
+
+            let mut buf = ptr::null_mut();
+            let mut len = 0;
+            assert_eq!(fid_serialize(handle, &mut buf, &mut len), FidStatus::Ok);
+            assert_eq!(
+                slice::from_raw_parts(buf, len),
+                &[11, 0, 0, 0, 0, 0, 0, 0, 0b1010_1010, 0b1110_0000]
+            );
+
+            let mut restored = ptr::null_mut();
+            assert_eq!(fid_deserialize(buf, len, &mut restored), FidStatus::Ok);
+            for i in 0..11 {
+                let (mut expected, mut actual) = (false, false);
+                assert_eq!(fid_get(handle, i, &mut expected), FidStatus::Ok);
+                assert_eq!(fid_get(restored, i, &mut actual), FidStatus::Ok);
+                assert_eq!(actual, expected);
+            }
+
+            fid_bytes_free(buf, len);
+            fid_free(restored);
+            fid_free(handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod fid_deserialize_failure_tests {
+    use super::*;
+
+    #[test]
+    fn too_short() {
+        unsafe {
+            let buf = [5u8, 0, 0, 0];
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                fid_deserialize(buf.as_ptr(), buf.len(), &mut handle),
+                FidStatus::InvalidArgument
+            );
+        }
+    }
+
+    #[test]
+    fn truncated_bits() {
+        unsafe {
+            let buf = [9u8, 0, 0, 0, 0, 0, 0, 0, 0xff];
+            let mut handle = ptr::null_mut();
+            assert_eq!(
+                fid_deserialize(buf.as_ptr(), buf.len(), &mut handle),
+                FidStatus::InvalidArgument
+            );
+        }
+    }
+}
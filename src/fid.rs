@@ -1,11 +1,35 @@
-mod block;
-mod blocks;
-mod chunk;
 mod chunks;
+#[cfg(feature = "arrow")]
+mod fid_arrow;
+#[cfg(feature = "bitvec")]
+mod fid_bitvec;
+mod fid_cmp;
+mod fid_conv;
+mod fid_encoding;
+mod fid_error;
+#[cfg(feature = "std")]
+mod fid_external;
+mod fid_fmt;
 mod fid_impl;
+#[cfg(feature = "std")]
+mod fid_io;
 mod fid_iter;
+mod fid_positions;
+#[cfg(feature = "rkyv")]
+mod fid_rkyv;
+mod fid_runs;
+#[cfg(feature = "serde")]
+mod fid_serde;
+mod fid_slice;
+mod fid_validate;
+mod mapped_fid;
+mod space_report;
+
+#[cfg(feature = "serde")]
+pub use fid_serde::bit_string;
 
 use super::internal_data_structure::popcount_table::PopcountTable;
+use alloc::vec::Vec;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -23,7 +47,7 @@ use mem_dbg::{MemDbg, MemSize};
 /// # Implementation detail
 /// [Index&lt;u64&gt;](#impl-Index<u64>)'s implementation is trivial.
 ///
-/// [select()](#method.select) just uses binary search of `rank()` results.
+/// [select1()](#method.select1) and [select0()](#method.select0) just use binary search of `rank()` results.
 ///
 /// [rank()](#method.rank)'s implementation is standard but non-trivial.
 /// So here explains implementation of _rank()_.
@@ -100,9 +124,12 @@ use mem_dbg::{MemDbg, MemSize};
 /// In summary:
 ///
 ///   _rank() = (value of left chunk) + (value of left block) + (value of table keyed by inner block bits)_.
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
 #[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct Fid {
     /// Raw data.
     byte_vec: Vec<u8>,
@@ -110,6 +137,9 @@ pub struct Fid {
     /// Bit length
     bit_len: u64,
 
+    /// Bit order in each byte of `byte_vec`.
+    bit_order: BitOrder,
+
     /// Total popcount of _[0, <u>last bit of the chunk</u>]_.
     ///
     /// Each chunk takes _2^64_ at max (when every bit is '1' for bit vector of length of _2^64_).
@@ -120,47 +150,289 @@ pub struct Fid {
     table: PopcountTable,
 }
 
-pub struct FidIter<'iter> {
-    fid: &'iter Fid,
-    i: u64,
-}
-
-/// Collection of Chunk.
-#[derive(Clone, Debug)]
+/// Order of bits in each byte of packed bits.
+///
+/// ```text
+/// bits:     0 1 0 0 1
+/// MsbFirst: 0b0100_1000
+/// LsbFirst: 0b0001_0010
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
-struct Chunks {
-    chunks: Vec<Chunk>,
-    chunks_cnt: u64,
+#[cfg_attr(feature = "mem_dbg", copy_type)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub enum BitOrder {
+    /// _i_-th bit is the _(7 - i % 8)_-th bit of the _(i / 8)_-th byte. Used by `Fid::from::<&[bool]>()`.
+    #[default]
+    MsbFirst,
+
+    /// _i_-th bit is the _(i % 8)_-th bit of the _(i / 8)_-th byte. Used by Arrow, Roaring and little endian words.
+    LsbFirst,
 }
 
-/// Total popcount of _[0, <u>last bit of the chunk</u>]_ of a bit vector.
+/// Breakdown of the heap memory used by a [Fid](struct.Fid.html).
 ///
-/// Each chunk takes _2^64_ at max (when every bit is '1' for Fid of length of _2^64_).
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
-struct Chunk {
-    value: u64, // popcount
-    blocks: Blocks,
+/// Every size is in bytes and counts allocated capacity, not only the used length.
+///
+/// # Examples
+/// ```
+/// use fid_rs::Fid;
+///
+/// let fid = Fid::from("0100_1");
+/// let report = fid.space_report();
+/// assert_eq!(report.bit_len, 5);
+/// assert_eq!(report.total(), fid.heap_size());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpaceReport {
+    /// Bit length of the FID.
+    pub bit_len: u64,
+
+    /// Bytes used by the raw bit vector.
+    pub raw_bits: usize,
+
+    /// Bytes used by the chunk directory (one entry per chunk).
+    pub chunk_directory: usize,
+
+    /// Bytes used by the block directory (one entry per block in every chunk).
+    pub block_directory: usize,
+
+    /// Bytes used by the table for inner-block `rank()`.
+    pub popcount_table: usize,
+
+    /// Bytes used by sampled `select1()`/`select0()` answers.
+    ///
+    /// Always _0_ for now since `select1()` is a binary search of `rank()`.
+    pub select_samples: usize,
 }
 
-/// Collection of Block in a Chunk.
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
-struct Blocks {
-    blocks: Vec<Block>,
-    blocks_cnt: u16,
+/// Error returned by fallible constructors of [Fid](struct.Fid.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FidError {
+    /// Bit length is _0_, while a `Fid` has at least 1 bit.
+    ZeroLength,
+
+    /// `position` is not less than the bit length `len`.
+    PositionOutOfRange {
+        /// The offending position.
+        position: u64,
+        /// Bit length of the `Fid` being built.
+        len: u64,
+    },
+
+    /// `position` came after a larger position `previous` in input which must be in ascending order.
+    UnsortedPositions {
+        /// The position just before `position`.
+        previous: u64,
+        /// The offending position.
+        position: u64,
+    },
+
+    /// Range _[`start`, `end`)_ is reversed (_`start` > `end`_) or exceeds the bit length `len`.
+    InvalidRange {
+        /// Start of the offending range.
+        start: u64,
+        /// End (exclusive) of the offending range.
+        end: u64,
+        /// Bit length of the `Fid` being built.
+        len: u64,
+    },
+
+    /// Total bit length of the input (e.g. the sum of run lengths) exceeds `u64::MAX`.
+    LengthOverflow,
+
+    /// Input has an unexpected character, e.g. other than '0', '1', '_' and whitespaces in text input.
+    InvalidCharacter {
+        /// The offending byte.
+        byte: u8,
+        /// Offset (0-origin) of `byte` in the input.
+        offset: u64,
+    },
+
+    /// A line of positions input is not a `u64` number.
+    InvalidPosition {
+        /// Line number (1-origin) of the offending line.
+        line: u64,
+    },
+
+    /// Input ended before `expected` bytes are read.
+    TruncatedInput {
+        /// Number of bytes required.
+        expected: u64,
+        /// Number of bytes actually read.
+        actual: u64,
+    },
+
+    /// Text encoding does not start with `<bit length>:`, where the bit length is a decimal `u64`.
+    InvalidBitLength,
+
+    /// Text encoding has `actual` characters after `<bit length>:` while the bit length requires `expected`.
+    EncodedLengthMismatch {
+        /// Number of characters the bit length requires.
+        expected: u64,
+        /// Number of characters actually given.
+        actual: u64,
+    },
+
+    /// Some of the unused bits in the last byte, after `bit_len` bits, are _1_.
+    NonZeroPaddingBits {
+        /// Bit length of the `Fid` being built.
+        bit_len: u64,
+    },
+
+    /// Input is not in the expected format.
+    Corrupted {
+        /// What is wrong with the input.
+        reason: &'static str,
+    },
+
+    /// Reading input failed.
+    #[cfg(feature = "std")]
+    Io {
+        /// Kind of the underlying `std::io::Error`.
+        kind: std::io::ErrorKind,
+        /// Message of the underlying `std::io::Error`.
+        message: alloc::string::String,
+    },
+}
+
+/// Format of input for [Fid::from_reader()](struct.Fid.html#method.from_reader).
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadFormat {
+    /// ASCII '0' and '1'. '_' and whitespaces are ignored.
+    Text,
+
+    /// Packed bits of `bit_len` bits in `bit_order`, i.e. _ceil(`bit_len` / 8)_ bytes. Following bytes are not read.
+    Bytes {
+        /// Bit length of the `Fid`.
+        bit_len: u64,
+        /// Order of bits in each byte.
+        bit_order: BitOrder,
+    },
+
+    /// Positions of _1_ in decimal, one per line and in any order. Blank lines are ignored.
+    Positions {
+        /// Bit length of the `Fid`.
+        len: u64,
+    },
+}
+
+/// Builder writing a FID file, which [MappedFid](struct.MappedFid.html) reads, from input larger than memory.
+///
+/// Input is read in segments and the packed bits and the rank directory are written out as they are computed,
+/// so that working memory is about 4 times [buffer_size()](#method.buffer_size) regardless of the input size.
+///
+/// # Examples
+/// ```
+/// use fid_rs::{ExternalBuilder, MappedFid, ReadFormat};
+/// use std::io::Cursor;
+///
+/// let mut file = Cursor::new(Vec::new());
+/// ExternalBuilder::new()
+///     .buffer_size(4096)
+///     .build(Cursor::new("0100_1"), ReadFormat::Text, &mut file)
+///     .unwrap();
+///
+/// let fid = MappedFid::from_bytes(file.into_inner()).unwrap();
+/// assert_eq!(fid.rank(4), 2);
+/// assert_eq!(fid.select1(1), Some(4));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct ExternalBuilder {
+    buffer_size: usize,
 }
 
-/// Total popcount of _[_first bit of the chunk which the block belongs to_, _last bit of the block_]_ of a bit vector.
+/// FID answering queries directly on bytes in the file format written by [ExternalBuilder](struct.ExternalBuilder.html).
 ///
-/// Each block takes (log 2^64)^2 = 64^2 = 2^16 at max (when every bit in a chunk is 1 for Fid of length of 2^64)
+/// `B` is typically a memory-mapped file (see [MappedFid::open()](#method.open) with `mmap` feature),
+/// so that only the pages touched by queries are read from disk.
 #[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MappedFid<B> {
+    bytes: B,
+    layout: MappedFidLayout,
+}
+
+/// Offsets and sizes of the sections of a FID file of `bit_len` bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct MappedFidLayout {
+    bit_len: u64,
+    chunk_size: u64,
+    block_size: u64,
+    blocks_per_chunk: u64,
+    chunks_offset: u64,
+    blocks_offset: u64,
+    file_len: u64,
+}
+
+pub struct FidIter<'iter> {
+    fid: &'iter Fid,
+    i: u64,
+}
+
+/// Borrowed view of _[`start`, `start` + `len`)_ of a [Fid](struct.Fid.html), created by [Fid::slice()](struct.Fid.html#method.slice).
+///
+/// Positions and counts are relative to the start of the slice.
+/// Queries reuse the index of the parent `Fid`, so a slice is as fast as the `Fid` and costs no extra memory.
+#[derive(Clone, Copy, Debug)]
+pub struct FidSlice<'fid> {
+    fid: &'fid Fid,
+    start: u64,
+    len: u64,
+
+    /// Number of _1_ in _[0, `start`)_ of `fid`.
+    rank_before_start: u64,
+}
+
+/// Iterator over bits of a [FidSlice](struct.FidSlice.html).
+pub struct FidSliceIter<'fid> {
+    slice: FidSlice<'fid>,
+    i: u64,
+}
+
+/// Iterator over positions of _1_ in a [FidSlice](struct.FidSlice.html), created by [FidSlice::ones()](struct.FidSlice.html#method.ones).
+pub struct Ones<'fid> {
+    slice: FidSlice<'fid>,
+
+    /// Index of the word (64 bits from the start of the parent `Fid`) `word` is taken from.
+    i_word: u64,
+
+    /// Remaining _1_s of the current word in MSB-first order.
+    word: u64,
+}
+
+/// Iterator over maximal runs of the same bit in a [Fid](struct.Fid.html), created by [Fid::runs()](struct.Fid.html#method.runs).
+pub struct Runs<'fid> {
+    fid: &'fid Fid,
+
+    /// Start of the next run.
+    pos: u64,
+}
+
+/// Rank directory: values of every chunk and block in flat vectors.
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
-struct Block {
-    value: u16, // popcount
-    length: u8,
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+struct Chunks {
+    /// `chunks[i]` is the total popcount of _[0, <u>last bit of `i`-th chunk</u>]_.
+    ///
+    /// Each chunk takes _2^64_ at max (when every bit is '1' for Fid of length of _2^64_).
+    chunks: Vec<u64>,
+
+    /// Blocks of all chunks in a row: `blocks[i_chunk * blocks_per_chunk + i_block]` is the total popcount of
+    /// _[<u>first bit of `i_chunk`-th chunk</u>, <u>last bit of `i_block`-th block in the chunk</u>]_.
+    ///
+    /// Blocks do not cross chunks, so the last block of a chunk may be shorter than the block size.
+    /// Each block takes (log 2^64)^2 = 64^2 = 2^16 at max (when every bit in a chunk is 1 for Fid of length of 2^64).
+    blocks: Vec<u16>,
 }
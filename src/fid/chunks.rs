@@ -1,120 +1,198 @@
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
-use super::{Chunk, Chunks};
+use super::Chunks;
 use crate::internal_data_structure::raw_bit_vector::RawBitVector;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
 
-impl super::Chunks {
-    /// Constructor.
-    #[cfg(feature = "rayon")]
-    pub fn new(rbv: &RawBitVector) -> Chunks {
-        let n = rbv.len();
-        let chunk_size: u16 = Chunks::calc_chunk_size(n);
-        let chunks_cnt: usize = Chunks::calc_chunks_cnt(n) as usize;
-
-        // In order to use chunks.par_iter_mut(), chunks should have len first.
-        // So fill meaning less None value.
-        let mut chunks: Vec<Chunk> = Vec::with_capacity(chunks_cnt);
-
-        // Parallel - Each chunk has its popcount.
-        //     Actually, chunk should have total popcount from index 0 but it is calculated later in sequential manner.
-        (0..chunks_cnt)
-            .into_par_iter()
-            .map(|number_of_chunk| {
-                let this_chunk_size: u16 = if number_of_chunk == chunks_cnt - 1 {
-                    // When `chunk_size == 6`:
-                    //
-                    //  000 111 000 11   : rbv
-                    // |       |      |  : chunks
-                    //
-                    // Here, when `i_chunk == 1` (targeting on last '00011' chunk),
-                    // `this_chunk_size == 5`
-                    let chunk_size_or_0 = (n % chunk_size as u64) as u16;
-                    if chunk_size_or_0 == 0 {
-                        chunk_size
-                    } else {
-                        chunk_size_or_0
-                    }
-                } else {
-                    chunk_size
-                };
-
-                let chunk_rbv = rbv.clone_sub(
-                    number_of_chunk as u64 * chunk_size as u64,
-                    this_chunk_size as u64,
-                );
-
-                let popcnt_in_chunk = chunk_rbv.popcount();
-                Chunk::new(
-                    popcnt_in_chunk,
-                    this_chunk_size,
-                    rbv,
-                    number_of_chunk as u64,
-                )
-            })
-            .collect_into_vec(&mut chunks);
-
-        // Sequential - Each chunk has total popcount from index 0.
-        for i_chunk in 0..chunks_cnt {
-            chunks[i_chunk].value += if i_chunk == 0 {
-                0
-            } else {
-                chunks[i_chunk - 1].value
+/// Number of chunks each task of construction takes.
+///
+/// Segments are fixed regardless of executor and number of threads, so that every executor makes the same directory.
+const SEGMENT_LEN: usize = 1 << 12;
+
+/// Runs tasks of construction.
+///
+/// An executor only decides where and in which order tasks run. It must return the results in the order of `items`.
+pub(super) trait Executor {
+    fn map<T, R, F>(&self, items: Vec<T>, f: F) -> Vec<R>
+    where
+        T: Send,
+        R: Send,
+        F: Fn(T) -> R + Sync + Send;
+}
+
+/// Runs tasks one by one in the current thread.
+pub(super) struct Sequential;
+
+impl Executor for Sequential {
+    fn map<T, R, F>(&self, items: Vec<T>, f: F) -> Vec<R>
+    where
+        T: Send,
+        R: Send,
+        F: Fn(T) -> R + Sync + Send,
+    {
+        items.into_iter().map(f).collect()
+    }
+}
+
+/// Runs tasks in the current rayon thread pool.
+#[cfg(feature = "rayon")]
+pub(super) struct Rayon;
+
+#[cfg(feature = "rayon")]
+impl Executor for Rayon {
+    fn map<T, R, F>(&self, items: Vec<T>, f: F) -> Vec<R>
+    where
+        T: Send,
+        R: Send,
+        F: Fn(T) -> R + Sync + Send,
+    {
+        items.into_par_iter().map(f).collect()
+    }
+}
+
+#[cfg(feature = "rayon")]
+const DEFAULT_EXECUTOR: Rayon = Rayon;
+#[cfg(not(feature = "rayon"))]
+const DEFAULT_EXECUTOR: Sequential = Sequential;
+
+/// Builds the directory from positions of _1_, without reading bits.
+///
+/// Each position is counted into its chunk and block by [count_one()](#method.count_one),
+/// then [finish()](#method.finish) turns the counts into prefix sums in a pass over the directory.
+pub(super) struct ChunksBuilder {
+    chunk_size: u64,
+    block_size: u64,
+    blocks_per_chunk: u64,
+    chunks: Vec<u64>,
+    blocks: Vec<u16>,
+}
+
+impl ChunksBuilder {
+    /// Returns a builder for `n` bits of _0_.
+    pub(super) fn new(n: u64) -> Self {
+        ChunksBuilder {
+            chunk_size: Chunks::calc_chunk_size(n) as u64,
+            block_size: Chunks::calc_block_size(n) as u64,
+            blocks_per_chunk: Chunks::calc_blocks_per_chunk(n),
+            chunks: vec![0; Chunks::calc_chunks_cnt(n) as usize],
+            blocks: vec![0; Chunks::calc_blocks_cnt(n) as usize],
+        }
+    }
+
+    /// Counts _1_ at `i`. Each position must be counted at most once.
+    pub(super) fn count_one(&mut self, i: u64) {
+        let i_chunk = i / self.chunk_size;
+        let i_block = i_chunk * self.blocks_per_chunk + (i % self.chunk_size) / self.block_size;
+        self.chunks[i_chunk as usize] += 1;
+        self.blocks[i_block as usize] += 1;
+    }
+
+    pub(super) fn finish(mut self) -> Chunks {
+        let mut sum = 0;
+        for (chunk, blocks) in self
+            .chunks
+            .iter_mut()
+            .zip(self.blocks.chunks_mut(self.blocks_per_chunk as usize))
+        {
+            let mut popcount_in_chunk = 0;
+            for block in blocks {
+                popcount_in_chunk += *block;
+                *block = popcount_in_chunk;
             }
+            sum += *chunk;
+            *chunk = sum;
         }
         Chunks {
-            chunks,
-            chunks_cnt: chunks_cnt as u64,
+            chunks: self.chunks,
+            blocks: self.blocks,
         }
     }
+}
 
-    /// Constructor.
-    #[cfg(not(feature = "rayon"))]
+impl super::Chunks {
+    /// Builds the directory in a pass over `rbv`, in parallel with `rayon` feature when there are multiple segments.
     pub fn new(rbv: &RawBitVector) -> Chunks {
+        if Chunks::calc_chunks_cnt(rbv.len()) <= SEGMENT_LEN as u64 {
+            Chunks::new_with(rbv, &Sequential)
+        } else {
+            Chunks::new_with(rbv, &DEFAULT_EXECUTOR)
+        }
+    }
+
+    /// Builds the directory in a pass over `rbv` by `executor`.
+    ///
+    /// Chunks are split into segments of `SEGMENT_LEN` chunks.
+    ///
+    /// 1. For each segment, popcounts of blocks are taken by a 64-bit window each
+    ///    and accumulated into the block values and the popcount of each chunk.
+    ///    Chunk values are turned into prefix sums inside the segment.
+    /// 2. Popcounts of the preceding segments are summed up.
+    /// 3. For each segment, the sum of 2. is added to its chunk values.
+    ///
+    /// Only 1. and 3. are run by `executor`. Since segments and the order of additions are fixed,
+    /// the directory is the same for any executor.
+    pub(super) fn new_with<E: Executor>(rbv: &RawBitVector, executor: &E) -> Chunks {
         let n = rbv.len();
-        let chunk_size: u16 = Chunks::calc_chunk_size(n);
-        let chunks_cnt: u64 = Chunks::calc_chunks_cnt(n);
-
-        let mut chunks: Vec<Chunk> = Vec::with_capacity(chunks_cnt as usize);
-        let mut comulative_popcount = 0;
-
-        for i_chunk in 0..chunks_cnt {
-            let this_chunk_size: u16 = if i_chunk == chunks_cnt - 1 {
-                // When `chunk_size == 6`:
-                //
-                //  000 111 000 11   : rbv
-                // |       |      |  : chunks
-                //
-                // Here, when `i_chunk == 1` (targeting on last '00011' chunk),
-                // `this_chunk_size == 5`
-                let chunk_size_or_0 = (n % chunk_size as u64) as u16;
-                if chunk_size_or_0 == 0 {
-                    chunk_size
-                } else {
-                    chunk_size_or_0
+        let chunk_size = Chunks::calc_chunk_size(n) as u64;
+        let block_size = Chunks::calc_block_size(n) as u64;
+        let blocks_per_chunk = Chunks::calc_blocks_per_chunk(n) as usize;
+
+        let mut chunks: Vec<u64> = vec![0; Chunks::calc_chunks_cnt(n) as usize];
+        let mut blocks: Vec<u16> = vec![0; Chunks::calc_blocks_cnt(n) as usize];
+
+        // 1.
+        let segments: Vec<_> = chunks
+            .chunks_mut(SEGMENT_LEN)
+            .zip(blocks.chunks_mut(SEGMENT_LEN * blocks_per_chunk))
+            .enumerate()
+            .collect();
+        let segment_popcounts = executor.map(segments, |(i_segment, (chunks, blocks))| {
+            let mut popcount_in_segment = 0;
+            for (i, (chunk, blocks)) in chunks
+                .iter_mut()
+                .zip(blocks.chunks_mut(blocks_per_chunk))
+                .enumerate()
+            {
+                let chunk_start = (i_segment * SEGMENT_LEN + i) as u64 * chunk_size;
+                let chunk_end = (chunk_start + chunk_size).min(n);
+                let mut popcount_in_chunk = 0;
+                for (i_block, block) in blocks.iter_mut().enumerate() {
+                    let block_start = chunk_start + i_block as u64 * block_size;
+                    let block_end = (block_start + block_size).min(chunk_end);
+                    popcount_in_chunk += rbv.popcount_range(block_start, block_end - block_start);
+                    *block = popcount_in_chunk as u16;
                 }
-            } else {
-                chunk_size
-            };
-
-            let chunk_rbv = rbv.clone_sub(i_chunk * chunk_size as u64, this_chunk_size as u64);
-
-            let popcnt_in_chunk = chunk_rbv.popcount();
-            comulative_popcount += popcnt_in_chunk;
-            chunks.push(Chunk::new(
-                comulative_popcount,
-                this_chunk_size,
-                rbv,
-                i_chunk,
-            ));
-        }
-        
-        Chunks { chunks, chunks_cnt }
+                popcount_in_segment += popcount_in_chunk;
+                *chunk = popcount_in_segment;
+            }
+            popcount_in_segment
+        });
+
+        // 2.
+        let mut sum = 0;
+        let offsets: Vec<u64> = segment_popcounts
+            .into_iter()
+            .map(|popcount| {
+                sum += popcount;
+                sum - popcount
+            })
+            .collect();
+
+        // 3.
+        let segments: Vec<(&mut [u64], u64)> =
+            chunks.chunks_mut(SEGMENT_LEN).zip(offsets).collect();
+        executor.map(segments, |(chunks, offset)| {
+            chunks.iter_mut().for_each(|chunk| *chunk += offset)
+        });
+
+        Chunks { chunks, blocks }
     }
 
-    /// Returns size of 1 chunk: _(log N)^2_.
     pub fn calc_chunk_size(n: u64) -> u16 {
-        let lg2 = (n as f64).log2() as u16;
+        let lg2 = n.checked_ilog2().unwrap_or(0) as u16;
         let sz = lg2 * lg2;
         if sz == 0 {
             1
@@ -123,26 +201,54 @@ impl super::Chunks {
         }
     }
 
-    /// Returns count of chunks: _N / (log N)^2_.
-    ///
-    /// At max: N / (log N)^2 = 2^64 / 64^2 = 2^(64-12)
+    /// Returns size of 1 block: _(log N) / 2_
+    pub fn calc_block_size(n: u64) -> u8 {
+        let lg2 = n.checked_ilog2().unwrap_or(0) as u8;
+        let sz = lg2 / 2;
+        if sz == 0 {
+            1
+        } else {
+            sz
+        }
+    }
+
     pub fn calc_chunks_cnt(n: u64) -> u64 {
-        let chunk_size = Chunks::calc_chunk_size(n);
-        n / (chunk_size as u64) + if n % (chunk_size as u64) == 0 { 0 } else { 1 }
+        n.div_ceil(Chunks::calc_chunk_size(n) as u64)
     }
 
-    /// Returns i-th chunk.
-    ///
-    /// # Panics
-    /// When _`i` >= `self.chunks_cnt()`_.
-    pub fn access(&self, i: u64) -> &Chunk {
-        assert!(
-            i <= self.chunks_cnt,
-            "i = {} must be smaller then {} (self.chunks_cnt())",
-            i,
-            self.chunks_cnt
-        );
-        &self.chunks[i as usize]
+    /// Returns the number of blocks in a chunk except the last chunk.
+    pub fn calc_blocks_per_chunk(n: u64) -> u64 {
+        (Chunks::calc_chunk_size(n) as u64).div_ceil(Chunks::calc_block_size(n) as u64)
+    }
+
+    /// Returns the number of blocks in all chunks.
+    pub fn calc_blocks_cnt(n: u64) -> u64 {
+        let chunk_size = Chunks::calc_chunk_size(n) as u64;
+        let chunks_cnt = Chunks::calc_chunks_cnt(n);
+        if chunks_cnt == 0 {
+            return 0;
+        }
+        let last_chunk_len = n - (chunks_cnt - 1) * chunk_size;
+        (chunks_cnt - 1) * Chunks::calc_blocks_per_chunk(n)
+            + last_chunk_len.div_ceil(Chunks::calc_block_size(n) as u64)
+    }
+
+    /// Returns the value of `i`-th chunk.
+    pub fn chunk(&self, i: u64) -> u64 {
+        self.chunks[i as usize]
+    }
+
+    /// Returns the value of `i`-th block, counting blocks of all chunks in a row.
+    pub fn block(&self, i: u64) -> u16 {
+        self.blocks[i as usize]
+    }
+
+    pub fn chunks_heap_size(&self) -> usize {
+        self.chunks.capacity() * mem::size_of::<u64>()
+    }
+
+    pub fn blocks_heap_size(&self) -> usize {
+        self.blocks.capacity() * mem::size_of::<u16>()
     }
 }
 
@@ -171,8 +277,7 @@ mod new_success_tests {
                 assert_eq!(Chunks::calc_chunk_size(n), input.expected_chunk_size);
                 assert_eq!(Chunks::calc_chunks_cnt(n), input.expected_chunks.len() as u64);
                 for (i, expected_chunk) in input.expected_chunks.iter().enumerate() {
-                    let chunk = chunks.access(i as u64);
-                    assert_eq!(chunk.value(), *expected_chunk);
+                    assert_eq!(chunks.chunk(i as u64), *expected_chunk);
                 }
             }
         )*
@@ -239,3 +344,138 @@ mod new_success_tests {
         },
     }
 }
+
+#[cfg(test)]
+mod directory_success_tests {
+    use super::Chunks;
+    use crate::internal_data_structure::raw_bit_vector::RawBitVector;
+    use alloc::vec::Vec;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let n: u64 = $value;
+                let bits: Vec<bool> = (0..n).map(|i| (i * i + i / 7) % 5 < 2).collect();
+                let mut byte_vec = vec![0u8; n.div_ceil(8) as usize];
+                for (i, _) in bits.iter().enumerate().filter(|(_, &b)| b) {
+                    byte_vec[i / 8] |= 0x80 >> (i % 8);
+                }
+                let last_byte_len = ((n - 1) % 8 + 1) as u8;
+                let rbv = RawBitVector::new(&byte_vec, 0, last_byte_len);
+                let chunks = Chunks::new(&rbv);
+
+                let chunk_size = Chunks::calc_chunk_size(n) as u64;
+                let block_size = Chunks::calc_block_size(n) as u64;
+                let blocks_per_chunk = Chunks::calc_blocks_per_chunk(n);
+                let rank = |start: u64, end: u64| bits[start as usize..end as usize].iter().filter(|&&b| b).count() as u64;
+
+                assert_eq!(chunks.chunks.len() as u64, Chunks::calc_chunks_cnt(n));
+                assert_eq!(chunks.blocks.len() as u64, Chunks::calc_blocks_cnt(n));
+                for i_chunk in 0..Chunks::calc_chunks_cnt(n) {
+                    let chunk_start = i_chunk * chunk_size;
+                    let chunk_end = (chunk_start + chunk_size).min(n);
+                    assert_eq!(chunks.chunk(i_chunk), rank(0, chunk_end), "i_chunk = {}", i_chunk);
+
+                    let blocks_cnt = (chunk_end - chunk_start).div_ceil(block_size);
+                    for i_block in 0..blocks_cnt {
+                        let block_end = (chunk_start + (i_block + 1) * block_size).min(chunk_end);
+                        assert_eq!(
+                            chunks.block(i_chunk * blocks_per_chunk + i_block) as u64,
+                            rank(chunk_start, block_end),
+                            "i_chunk = {}, i_block = {}", i_chunk, i_block
+                        );
+                    }
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: 1,
+        t2: 2,
+        t3: 7,
+        t4: 8,
+        t5: 25,
+        t6: 63,
+        t7: 64,
+        t8: 65,
+        t9: 1000,
+        t10: 4096,
+        t11: 100_000,
+        t12: 1 << 20,
+    }
+}
+
+#[cfg(test)]
+mod executor_success_tests {
+    use super::{Chunks, Sequential};
+    use crate::internal_data_structure::raw_bit_vector::RawBitVector;
+    use alloc::vec::Vec;
+
+    /// Pseudo random bytes by xorshift, the same on every platform.
+    fn bytes(len: usize) -> Vec<u8> {
+        let mut x: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x as u8
+            })
+            .collect()
+    }
+
+    /// FNV-1a hash of the directory.
+    fn digest(chunks: &Chunks) -> u64 {
+        let chunk_bytes = chunks.chunks.iter().flat_map(|c| c.to_le_bytes());
+        let block_bytes = chunks.blocks.iter().flat_map(|b| b.to_le_bytes());
+        chunk_bytes
+            .chain(block_bytes)
+            .fold(0xcbf2_9ce4_8422_2325, |h, byte| {
+                (h ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (n, expected_digest): (u64, u64) = $value;
+                let byte_vec = bytes(n.div_ceil(8) as usize);
+                let rbv = RawBitVector::new(&byte_vec, 0, ((n - 1) % 8 + 1) as u8);
+
+                let sequential = Chunks::new_with(&rbv, &Sequential);
+                let default = Chunks::new(&rbv);
+                assert_eq!(default.chunks, sequential.chunks);
+                assert_eq!(default.blocks, sequential.blocks);
+
+                // Same digest whichever features this test is built with.
+                assert_eq!(digest(&default), expected_digest);
+
+                #[cfg(feature = "rayon")]
+                for num_threads in [1, 2, 3, 8] {
+                    let pool = rayon::ThreadPoolBuilder::new()
+                        .num_threads(num_threads)
+                        .build()
+                        .unwrap();
+                    let parallel = pool.install(|| Chunks::new_with(&rbv, &super::Rayon));
+                    assert_eq!(parallel.chunks, sequential.chunks, "num_threads = {}", num_threads);
+                    assert_eq!(parallel.blocks, sequential.blocks, "num_threads = {}", num_threads);
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (1, 0x5131_4cc8_9da9_b06d),
+        t2: (1000, 0xe67a_5fe4_2fb3_b765),
+        t3: (1 << 20, 0x2339_7e88_1bc4_a3ca),
+        t4: (1 << 22, 0x5a2a_3ecb_45cb_871f),
+        t5: ((1 << 22) + 12345, 0x76cd_8340_bbec_f439),
+    }
+}
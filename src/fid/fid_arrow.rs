@@ -0,0 +1,186 @@
+use super::{BitOrder, Fid};
+use arrow_array::{Array, BooleanArray};
+use arrow_buffer::{BooleanBuffer, Buffer, NullBuffer};
+
+impl From<&BooleanBuffer> for Fid {
+    /// Constructor from Arrow's `BooleanBuffer` (LSB-first bitmap with a bit offset).
+    ///
+    /// Bits are read 64 at a time. Built `Fid` is in `BitOrder::LsbFirst`, the same as Arrow.
+    ///
+    /// # Examples
+    /// ```
+    /// use arrow_buffer::BooleanBuffer;
+    /// use fid_rs::Fid;
+    ///
+    /// let buf = BooleanBuffer::from(&[true, false, true, false, false, true][..]);
+    /// assert_eq!(Fid::from(&buf.slice(1, 5)), Fid::from("0100_1"));
+    /// ```
+    ///
+    /// # Panics
+    /// When `buf` is empty.
+    fn from(buf: &BooleanBuffer) -> Self {
+        assert!(!buf.is_empty());
+        Fid::from_words(buf.bit_chunks().iter_padded(), buf.len() as u64)
+    }
+}
+
+impl From<&NullBuffer> for Fid {
+    /// Constructor from Arrow's validity bitmap. _1_ is valid (non-null) and _0_ is null.
+    ///
+    /// `rank(i)` is the number of non-null values in _[0, `i`]_, and `select1(k)` is the physical index of the `k`-th non-null value.
+    ///
+    /// # Examples
+    /// ```
+    /// use arrow_buffer::NullBuffer;
+    /// use fid_rs::Fid;
+    ///
+    /// let nulls = NullBuffer::from(vec![false, true, false, false, true]);
+    /// let fid = Fid::from(&nulls);
+    /// assert_eq!(fid.select1(1), Some(4));
+    /// ```
+    ///
+    /// # Panics
+    /// When `nulls` is empty.
+    fn from(nulls: &NullBuffer) -> Self {
+        Fid::from(nulls.inner())
+    }
+}
+
+impl From<&BooleanArray> for Fid {
+    /// Constructor from Arrow's `BooleanArray`. Null values are _0_.
+    ///
+    /// # Examples
+    /// ```
+    /// use arrow_array::BooleanArray;
+    /// use fid_rs::Fid;
+    ///
+    /// let arr = BooleanArray::from(vec![Some(false), Some(true), None, Some(false), Some(true)]);
+    /// assert_eq!(Fid::from(&arr), Fid::from("0100_1"));
+    /// ```
+    ///
+    /// # Panics
+    /// When `arr` is empty.
+    fn from(arr: &BooleanArray) -> Self {
+        match arr.nulls() {
+            Some(nulls) => Fid::from(&(arr.values() & nulls.inner())),
+            None => Fid::from(arr.values()),
+        }
+    }
+}
+
+impl From<&Fid> for BooleanBuffer {
+    /// Converts into an Arrow bitmap of offset _0_.
+    ///
+    /// Bytes are copied as they are when [Fid::bit_order()](struct.Fid.html#method.bit_order) is `BitOrder::LsbFirst`.
+    fn from(fid: &Fid) -> Self {
+        let bytes: Vec<u8> = match fid.bit_order() {
+            BitOrder::LsbFirst => fid.as_bytes().to_vec(),
+            BitOrder::MsbFirst => fid
+                .to_words()
+                .iter()
+                .flat_map(|word| word.to_le_bytes())
+                .collect(),
+        };
+        BooleanBuffer::new(Buffer::from_vec(bytes), 0, fid.len() as usize)
+    }
+}
+
+impl From<&Fid> for NullBuffer {
+    /// Converts into an Arrow validity bitmap. _1_ is valid (non-null) and _0_ is null.
+    fn from(fid: &Fid) -> Self {
+        NullBuffer::new(BooleanBuffer::from(fid))
+    }
+}
+
+impl From<&Fid> for BooleanArray {
+    /// Converts into an Arrow `BooleanArray` without nulls.
+    ///
+    /// # Examples
+    /// ```
+    /// use arrow_array::{Array, BooleanArray};
+    /// use fid_rs::Fid;
+    ///
+    /// let arr = BooleanArray::from(&Fid::from("0100_1"));
+    /// assert_eq!(arr, BooleanArray::from(vec![false, true, false, false, true]));
+    /// assert_eq!(arr.null_count(), 0);
+    /// ```
+    fn from(fid: &Fid) -> Self {
+        BooleanArray::new(BooleanBuffer::from(fid), None)
+    }
+}
+
+#[cfg(test)]
+mod arrow_success_tests {
+    use crate::Fid;
+    use arrow_array::{Array, BooleanArray};
+    use arrow_buffer::{BooleanBuffer, NullBuffer};
+
+    fn sample_bits(len: usize) -> Vec<bool> {
+        (0..len).map(|i| i % 3 == 0 || i % 7 == 0).collect()
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_len, in_offset) = $value;
+                let bools = sample_bits(in_len + in_offset);
+                let expected = Fid::from(&bools[in_offset..]);
+
+                let buf = BooleanBuffer::from(&bools[..]).slice(in_offset, in_len);
+                assert_eq!(Fid::from(&buf), expected);
+                assert_eq!(Fid::from(&NullBuffer::new(buf.clone())), expected);
+                assert_eq!(Fid::from(&BooleanArray::new(buf.clone(), None)), expected);
+
+                assert_eq!(BooleanBuffer::from(&expected), buf);
+                assert_eq!(BooleanBuffer::from(&Fid::from(&buf)), buf);
+                assert_eq!(NullBuffer::from(&expected), NullBuffer::new(buf.clone()));
+                assert_eq!(BooleanArray::from(&expected), BooleanArray::new(buf, None));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (1, 0),
+        t2: (5, 0),
+        t3: (8, 0),
+        t4: (9, 0),
+        t5: (64, 0),
+        t6: (65, 0),
+        t7: (200, 0),
+        t8: (5, 3),
+        t9: (64, 1),
+        t10: (200, 13),
+    }
+
+    #[test]
+    fn nulls_are_zero() {
+        let arr = BooleanArray::from(vec![Some(true), None, Some(true), None]);
+        assert_eq!(arr.null_count(), 2);
+        assert_eq!(Fid::from(&arr), Fid::from("1010"));
+        assert_eq!(Fid::from(&arr.slice(1, 3)), Fid::from("010"));
+    }
+
+    #[test]
+    fn rank_and_select_over_validity() {
+        let arr = BooleanArray::from(vec![None, Some(false), None, Some(true), Some(true)]);
+        let validity = Fid::from(arr.nulls().unwrap());
+        // Logical row 1 (non-null) is physical row 3.
+        assert_eq!(validity.select1(1), Some(3));
+        assert_eq!(validity.rank(3), 2);
+    }
+}
+
+#[cfg(test)]
+mod arrow_failure_tests {
+    use crate::Fid;
+    use arrow_buffer::BooleanBuffer;
+
+    #[test]
+    #[should_panic]
+    fn empty() {
+        let _ = Fid::from(&BooleanBuffer::new_unset(0));
+    }
+}
@@ -0,0 +1,210 @@
+use super::{BitOrder, Fid};
+use bitvec::order::BitOrder as BitvecOrder;
+use bitvec::prelude::*;
+
+/// Copies `bits` into bytes of `bit_order` with _0_ in unused bits, shared by `Msb0` and `Lsb0` conversions.
+///
+/// # Panics
+/// When `bits` is empty.
+fn from_byte_bitslice<O: BitvecOrder>(bits: &BitSlice<u8, O>, bit_order: BitOrder) -> Fid {
+    assert!(!bits.is_empty());
+
+    let mut bv = BitVec::<u8, O>::from_bitslice(bits);
+    bv.force_align();
+    bv.set_uninitialized(false);
+    Fid::from_raw_parts(bv.into_vec(), bits.len() as u64, bit_order)
+}
+
+impl From<&BitSlice<u8, Msb0>> for Fid {
+    /// Constructor from `BitSlice<u8, Msb0>`. Built `Fid` is in `BitOrder::MsbFirst`.
+    ///
+    /// Copies bytes as they are when `bits` starts at a byte boundary.
+    ///
+    /// # Examples
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use fid_rs::Fid;
+    ///
+    /// let bits = bits![u8, Msb0; 0, 1, 0, 0, 1];
+    /// let fid = Fid::from(bits);
+    /// assert_eq!(fid, Fid::from("0100_1"));
+    /// ```
+    ///
+    /// # Panics
+    /// When `bits` is empty.
+    fn from(bits: &BitSlice<u8, Msb0>) -> Self {
+        from_byte_bitslice(bits, BitOrder::MsbFirst)
+    }
+}
+
+// `bitvec` supports `u64` storage only on 64-bit targets.
+#[cfg(target_pointer_width = "64")]
+impl From<BitVec<u64, Lsb0>> for Fid {
+    /// Constructor from `BitVec<u64, Lsb0>`. Available on 64-bit targets.
+    ///
+    /// Each word is converted to 8 bytes at once. Built `Fid` is in `BitOrder::LsbFirst`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use fid_rs::Fid;
+    ///
+    /// let bits = bitvec![u64, Lsb0; 0, 1, 0, 0, 1];
+    /// let fid = Fid::from(bits);
+    /// assert_eq!(fid, Fid::from("0100_1"));
+    /// ```
+    ///
+    /// # Panics
+    /// When `bits` is empty.
+    fn from(mut bits: BitVec<u64, Lsb0>) -> Self {
+        assert!(!bits.is_empty());
+
+        let bit_len = bits.len() as u64;
+        bits.force_align();
+        Fid::from_words(bits.into_vec(), bit_len)
+    }
+}
+
+impl From<&BitSlice<u8, Lsb0>> for Fid {
+    /// Constructor from `BitSlice<u8, Lsb0>`. Built `Fid` is in `BitOrder::LsbFirst`.
+    ///
+    /// Copies bytes as they are when `bits` starts at a byte boundary.
+    ///
+    /// # Examples
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use fid_rs::{BitOrder, Fid};
+    ///
+    /// let bits = bits![u8, Lsb0; 0, 1, 0, 0, 1];
+    /// let fid = Fid::from(bits);
+    /// assert_eq!(fid, Fid::from("0100_1"));
+    /// assert_eq!(fid.bit_order(), BitOrder::LsbFirst);
+    /// ```
+    ///
+    /// # Panics
+    /// When `bits` is empty.
+    fn from(bits: &BitSlice<u8, Lsb0>) -> Self {
+        from_byte_bitslice(bits, BitOrder::LsbFirst)
+    }
+}
+
+impl Fid {
+    /// Returns the bits as `BitSlice<u8, Msb0>` without copying, if [bit_order()](#method.bit_order) is `BitOrder::MsbFirst`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from("0100_1");
+    /// let bits = fid.as_bitslice().unwrap();
+    /// assert_eq!(bits, bits![0, 1, 0, 0, 1]);
+    /// assert_eq!(bits.count_ones(), 2);
+    /// ```
+    pub fn as_bitslice(&self) -> Option<&BitSlice<u8, Msb0>> {
+        match self.bit_order {
+            BitOrder::MsbFirst => Some(&self.byte_vec.view_bits()[..self.bit_len as usize]),
+            BitOrder::LsbFirst => None,
+        }
+    }
+
+    /// Returns the bits as `BitSlice<u8, Lsb0>` without copying, if [bit_order()](#method.bit_order) is `BitOrder::LsbFirst`.
+    pub fn as_lsb0_bitslice(&self) -> Option<&BitSlice<u8, Lsb0>> {
+        match self.bit_order {
+            BitOrder::MsbFirst => None,
+            BitOrder::LsbFirst => Some(&self.byte_vec.view_bits()[..self.bit_len as usize]),
+        }
+    }
+}
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod bitvec_success_tests {
+    use crate::Fid;
+    use alloc::vec::Vec;
+    use bitvec::prelude::*;
+
+    fn sample_bits(len: usize) -> Vec<bool> {
+        (0..len).map(|i| i % 3 == 0 || i % 7 == 0).collect()
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_len, in_offset) = $value;
+                let bools = sample_bits(in_len + in_offset);
+                let expected = Fid::from(&bools[in_offset..]);
+
+                let msb0: BitVec<u8, Msb0> = bools.iter().collect();
+                assert_eq!(Fid::from(&msb0[in_offset..]), expected);
+
+                let mut lsb0: BitVec<u64, Lsb0> = bools.iter().collect();
+                lsb0.drain(..in_offset);
+                assert_eq!(Fid::from(lsb0), expected);
+
+                let lsb0_u8: BitVec<u8, Lsb0> = bools.iter().collect();
+                let from_lsb0_u8 = Fid::from(&lsb0_u8[in_offset..]);
+                assert_eq!(from_lsb0_u8, expected);
+
+                assert_eq!(expected.as_bitslice(), Some(&msb0[in_offset..]));
+                assert_eq!(expected.as_lsb0_bitslice(), None);
+                assert_eq!(from_lsb0_u8.as_lsb0_bitslice(), Some(&lsb0_u8[in_offset..]));
+                assert_eq!(from_lsb0_u8.as_bitslice(), None);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (1, 0),
+        t2: (5, 0),
+        t3: (8, 0),
+        t4: (9, 0),
+        t5: (64, 0),
+        t6: (65, 0),
+        t7: (200, 0),
+        t8: (5, 3),
+        t9: (64, 1),
+        t10: (200, 13),
+    }
+
+    #[test]
+    fn ignores_bits_outside_slice() {
+        let bits = bits![u8, Msb0; 1, 1, 0, 1, 1, 1, 1, 1, 1, 1];
+        let fid = Fid::from(&bits[2..4]);
+        assert_eq!(fid, Fid::from("01"));
+        assert_eq!(fid.as_bytes(), &[0b0100_0000]);
+        assert_eq!(fid.validate(), Ok(()));
+
+        let bits = bits![u8, Lsb0; 1, 1, 0, 1, 1, 1, 1, 1, 1, 1];
+        let fid = Fid::from(&bits[2..4]);
+        assert_eq!(fid, Fid::from("01"));
+        assert_eq!(fid.as_bytes(), &[0b0000_0010]);
+        assert_eq!(fid.validate(), Ok(()));
+    }
+}
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod bitvec_failure_tests {
+    use crate::Fid;
+    use bitvec::prelude::*;
+
+    #[test]
+    #[should_panic]
+    fn empty_bitslice() {
+        let _ = Fid::from(BitSlice::<u8, Msb0>::empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_lsb0_bitslice() {
+        let _ = Fid::from(BitSlice::<u8, Lsb0>::empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_bitvec() {
+        let _ = Fid::from(BitVec::<u64, Lsb0>::new());
+    }
+}
@@ -0,0 +1,172 @@
+use super::Fid;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+impl Fid {
+    /// Returns _[64 * `i`, 64 * `i` + 64)_ bits as a big-endian word (the first bit is the MSB).
+    ///
+    /// Bits at or after `valid_len` (including padding bits in the last byte) are cleared to _0_.
+    pub(super) fn msb_word(&self, i: u64, valid_len: u64) -> u64 {
+        let start = (i * 8) as usize;
+        let end = self.byte_vec.len().min(start + 8);
+        let mut buf = [0u8; 8];
+        for (i_buf, i_byte) in (start..end).enumerate() {
+            buf[i_buf] = self.msb_first_byte(i_byte);
+        }
+        let word = u64::from_be_bytes(buf);
+
+        let word_len = valid_len - i * 64;
+        if word_len >= 64 {
+            word
+        } else {
+            word & !(u64::MAX >> word_len)
+        }
+    }
+}
+
+/// Two `Fid`s are equal iff they have the same length and the same bits.
+impl PartialEq for Fid {
+    fn eq(&self, other: &Self) -> bool {
+        self.bit_len == other.bit_len
+            && (0..self.bit_len.div_ceil(64))
+                .all(|i| self.msb_word(i, self.bit_len) == other.msb_word(i, other.bit_len))
+    }
+}
+
+impl Eq for Fid {}
+
+/// Consistent with `PartialEq`: hashes the length and the bits.
+impl Hash for Fid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bit_len.hash(state);
+        for i in 0..self.bit_len.div_ceil(64) {
+            self.msb_word(i, self.bit_len).hash(state);
+        }
+    }
+}
+
+impl PartialOrd for Fid {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Lexicographic order of bits as `[bool]`; _0_ < _1_, and a prefix is less than the longer one.
+///
+/// ```
+/// use fid_rs::Fid;
+///
+/// assert!(Fid::from("011") < Fid::from("1"));
+/// assert!(Fid::from("01") < Fid::from("010"));
+/// ```
+impl Ord for Fid {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let common_len = self.bit_len.min(other.bit_len);
+        (0..common_len.div_ceil(64))
+            .map(|i| {
+                self.msb_word(i, common_len)
+                    .cmp(&other.msb_word(i, common_len))
+            })
+            .find(|ord| ord.is_ne())
+            .unwrap_or_else(|| self.bit_len.cmp(&other.bit_len))
+    }
+}
+
+#[cfg(test)]
+mod fid_cmp_success_tests {
+    use crate::Fid;
+    use alloc::vec::Vec;
+    use core::cmp::Ordering;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(fid: &Fid) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        fid.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_lhs, in_rhs, expected_ord) = $value;
+                let (lhs, rhs) = (Fid::from(in_lhs), Fid::from(in_rhs));
+                assert_eq!(lhs.cmp(&rhs), expected_ord);
+                assert_eq!(rhs.cmp(&lhs), expected_ord.reverse());
+                assert_eq!(lhs == rhs, expected_ord == Ordering::Equal);
+                if lhs == rhs {
+                    assert_eq!(hash(&lhs), hash(&rhs));
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("0", "0", Ordering::Equal),
+        t2: ("0", "1", Ordering::Less),
+        t3: ("0", "00", Ordering::Less),
+        t4: ("1", "01", Ordering::Greater),
+        t5: ("0100_1", "0100_1", Ordering::Equal),
+        t6: ("0100_1", "0100_0", Ordering::Greater),
+        t7: ("0100_1", "0100_10", Ordering::Less),
+
+        t8: (
+            "00000000_00000000_00000000_00000000_00000000_00000000_00000000_00000000_1",
+            "00000000_00000000_00000000_00000000_00000000_00000000_00000000_00000000_0",
+            Ordering::Greater,
+        ),
+        t9: (
+            "10000000_00000000_00000000_00000000_00000000_00000000_00000000_00000000_0",
+            "01111111_11111111_11111111_11111111_11111111_11111111_11111111_11111111_1",
+            Ordering::Greater,
+        ),
+        t10: (
+            "11111111_11111111_11111111_11111111_11111111_11111111_11111111_11111111",
+            "11111111_11111111_11111111_11111111_11111111_11111111_11111111_11111111_0",
+            Ordering::Less,
+        ),
+    }
+
+    #[test]
+    fn ignores_padding_bits() {
+        let fid = Fid::from("0100_1");
+        let mut padded = fid.clone();
+        *padded.byte_vec.last_mut().unwrap() |= 0b0000_0111;
+
+        assert_eq!(fid, padded);
+        assert_eq!(fid.cmp(&padded), Ordering::Equal);
+        assert_eq!(hash(&fid), hash(&padded));
+        assert!(padded < Fid::from("0100_10"));
+    }
+
+    #[test]
+    fn same_as_vec_bool_order() {
+        let strs = [
+            "0",
+            "1",
+            "00",
+            "01",
+            "10",
+            "11",
+            "010",
+            "0100_1",
+            "1111_1111_1",
+        ];
+        for lhs in strs.iter() {
+            for rhs in strs.iter() {
+                let lhs_bits: Vec<bool> = Fid::from(*lhs).iter().collect();
+                let rhs_bits: Vec<bool> = Fid::from(*rhs).iter().collect();
+                assert_eq!(
+                    Fid::from(*lhs).cmp(&Fid::from(*rhs)),
+                    lhs_bits.cmp(&rhs_bits),
+                    "lhs = {}, rhs = {}",
+                    lhs,
+                    rhs
+                );
+            }
+        }
+    }
+}
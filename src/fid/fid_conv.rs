@@ -0,0 +1,159 @@
+use super::{BitOrder, Fid};
+use alloc::vec::Vec;
+
+impl Fid {
+    /// Returns the packed bits in [bit_order()](#method.bit_order) as they are.
+    ///
+    /// With `BitOrder::MsbFirst` (e.g. built by `Fid::from()`), _i_-th bit is the _(7 - i % 8)_-th bit of the _(i / 8)_-th byte.
+    ///
+    /// The length is _ceil(`len()` / 8)_. Unused bits in the last byte are _0_.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from("01001000_01");
+    /// assert_eq!(fid.as_bytes(), &[0b0100_1000, 0b0100_0000]);
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.byte_vec[..]
+    }
+
+    /// Returns the bits as `Vec<bool>`.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from("0100_1");
+    /// assert_eq!(fid.to_vec_bool(), vec![false, true, false, false, true]);
+    /// ```
+    pub fn to_vec_bool(&self) -> Vec<bool> {
+        let mut bits = Vec::with_capacity(self.bit_len as usize);
+        for i_byte in 0..self.byte_vec.len() {
+            let byte = self.msb_first_byte(i_byte);
+            let byte_len = (self.bit_len - bits.len() as u64).min(8);
+            bits.extend((0..byte_len).map(|i| byte & (0x80 >> i) != 0));
+        }
+        bits
+    }
+
+    /// Returns the bits packed into `u64` words: _i_-th bit is the _(i % 64)_-th bit (LSB-first) of the _(i / 64)_-th word.
+    ///
+    /// This is the layout of `bitvec::BitVec<u64, Lsb0>` and Arrow's bitmaps on little endian machines.
+    /// The length is _ceil(`len()` / 64)_. Unused bits in the last word are _0_.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from("0100_1");
+    /// assert_eq!(fid.to_words(), vec![0b1_0010]);
+    /// ```
+    pub fn to_words(&self) -> Vec<u64> {
+        (0..self.bit_len.div_ceil(64))
+            .map(|i| self.msb_word(i, self.bit_len).reverse_bits())
+            .collect()
+    }
+
+    /// Inverse of [to_words()](#method.to_words): builds a `Fid` of `bit_len` bits from LSB-first `u64` words.
+    ///
+    /// Words after _ceil(`bit_len` / 64)_ are ignored.
+    /// Built `Fid` is in `BitOrder::LsbFirst` so that no bits are reordered.
+    ///
+    /// # Panics
+    /// When:
+    /// - _`bit_len` == 0_.
+    /// - `words` has less than _ceil(`bit_len` / 64)_ words.
+    #[cfg(any(
+        all(feature = "bitvec", target_pointer_width = "64"),
+        feature = "arrow"
+    ))]
+    pub(crate) fn from_words(words: impl IntoIterator<Item = u64>, bit_len: u64) -> Self {
+        let byte_len = bit_len.div_ceil(8) as usize;
+        let mut byte_vec: Vec<u8> = words
+            .into_iter()
+            .take(bit_len.div_ceil(64) as usize)
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        assert!(byte_vec.len() >= byte_len);
+        byte_vec.truncate(byte_len);
+        Fid::from_raw_parts(byte_vec, bit_len, BitOrder::LsbFirst)
+    }
+
+    /// Decomposes into the packed bits (same as [as_bytes()](#method.as_bytes)), the bit length and the bit order.
+    ///
+    /// Inverse of [from_raw_parts()](#method.from_raw_parts).
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::{BitOrder, Fid};
+    ///
+    /// let (bytes, bit_len, bit_order) = Fid::from("0100_1").into_raw_parts();
+    /// assert_eq!(bytes, vec![0b0100_1000]);
+    /// assert_eq!(bit_len, 5);
+    /// assert_eq!(bit_order, BitOrder::MsbFirst);
+    /// ```
+    pub fn into_raw_parts(self) -> (Vec<u8>, u64, BitOrder) {
+        (self.byte_vec, self.bit_len, self.bit_order)
+    }
+}
+
+#[cfg(test)]
+mod conv_success_tests {
+    use crate::{BitOrder, Fid};
+    use alloc::vec::Vec;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let in_bits: &[bool] = $value;
+                let fid = Fid::from(in_bits);
+
+                assert_eq!(fid.to_vec_bool(), in_bits);
+
+                let bytes = fid.as_bytes();
+                assert_eq!(bytes.len(), in_bits.len().div_ceil(8));
+                for (i, &bit) in in_bits.iter().enumerate() {
+                    assert_eq!(bytes[i / 8] & (0x80 >> (i % 8)) != 0, bit, "i = {}", i);
+                }
+
+                let words = fid.to_words();
+                assert_eq!(words.len(), in_bits.len().div_ceil(64));
+                for (i, &bit) in in_bits.iter().enumerate() {
+                    assert_eq!(words[i / 64] & (1 << (i % 64)) != 0, bit, "i = {}", i);
+                }
+                let popcount: u32 = words.iter().map(|w| w.count_ones()).sum();
+                assert_eq!(popcount as usize, in_bits.iter().filter(|&&b| b).count());
+
+                let expected_bytes = bytes.to_vec();
+                assert_eq!(
+                    fid.into_raw_parts(),
+                    (expected_bytes, in_bits.len() as u64, BitOrder::MsbFirst)
+                );
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: &[false],
+        t2: &[true],
+        t3: &[false, true, false, false, true],
+        t4: &[true; 8],
+        t5: &[true; 9],
+        t6: &[true; 64],
+        t7: &[true; 65],
+        t8: &(0..200).map(|i| i % 3 == 0).collect::<Vec<bool>>(),
+    }
+
+    #[test]
+    fn words_ignore_padding_bits() {
+        let mut fid = Fid::from("0100_1");
+        *fid.byte_vec.last_mut().unwrap() |= 0b0000_0111;
+        assert_eq!(fid.to_words(), [0b1_0010]);
+        assert_eq!(fid.to_vec_bool(), [false, true, false, false, true]);
+    }
+}
@@ -0,0 +1,310 @@
+use super::{BitOrder, Fid, FidError};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Standard alphabet of RFC 4648.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl Fid {
+    /// Returns `"<bit length>:<hex>"`, where _\<hex\>_ is the packed bits in `BitOrder::MsbFirst`
+    /// (2 lowercase digits per byte, unused bits in the last byte are _0_).
+    ///
+    /// Inverse of [from_hex()](#method.from_hex).
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from("01001000_01");
+    /// assert_eq!(fid.to_hex(), "10:4840");
+    /// assert_eq!(Fid::from_hex(&fid.to_hex()), Ok(fid));
+    /// ```
+    pub fn to_hex(&self) -> String {
+        let mut s = self.encoding_prefix(self.byte_vec.len() * 2);
+        for i_byte in 0..self.byte_vec.len() {
+            let byte = self.msb_first_byte(i_byte);
+            s.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+            s.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+        }
+        s
+    }
+
+    /// Constructor from `"<bit length>:<hex>"` made by [to_hex()](#method.to_hex).
+    ///
+    /// Hex digits may be uppercase.
+    ///
+    /// # Errors
+    /// - `FidError::InvalidBitLength` when `s` does not start with a decimal `u64` followed by ':'.
+    /// - `FidError::ZeroLength` when the bit length is _0_.
+    /// - `FidError::EncodedLengthMismatch` when the number of hex digits is not _2 * ceil(bit length / 8)_.
+    /// - `FidError::InvalidCharacter` when a hex digit is not in _[0-9a-fA-F]_.
+    /// - `FidError::NonZeroPaddingBits` when a bit after the bit length is _1_.
+    pub fn from_hex(s: &str) -> Result<Self, FidError> {
+        let (bit_len, payload, payload_offset) = split_bit_len(s)?;
+        let byte_len = bit_len.div_ceil(8);
+        check_encoded_len(payload, byte_len * 2)?;
+
+        let digit = |i: usize| {
+            let byte = payload.as_bytes()[i];
+            (byte as char)
+                .to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(FidError::InvalidCharacter {
+                    byte,
+                    offset: (payload_offset + i) as u64,
+                })
+        };
+        let byte_vec = (0..byte_len as usize)
+            .map(|i_byte| Ok(digit(2 * i_byte)? << 4 | digit(2 * i_byte + 1)?))
+            .collect::<Result<Vec<u8>, FidError>>()?;
+        Fid::try_from_raw_parts(byte_vec, bit_len, BitOrder::MsbFirst)
+    }
+
+    /// Returns `"<bit length>:<base64>"`, where _\<base64\>_ is the packed bits in `BitOrder::MsbFirst`
+    /// (unused bits in the last byte are _0_) in base64 with the standard alphabet and padding of RFC 4648.
+    ///
+    /// Inverse of [from_base64()](#method.from_base64).
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from("01001000_01");
+    /// assert_eq!(fid.to_base64(), "10:SEA=");
+    /// assert_eq!(Fid::from_base64(&fid.to_base64()), Ok(fid));
+    /// ```
+    pub fn to_base64(&self) -> String {
+        let mut s = self.encoding_prefix(self.byte_vec.len().div_ceil(3) * 4);
+        for i_group in 0..self.byte_vec.len().div_ceil(3) {
+            let group_len = (self.byte_vec.len() - i_group * 3).min(3);
+            let mut group = [0u8; 3];
+            for (i, byte) in group[..group_len].iter_mut().enumerate() {
+                *byte = self.msb_first_byte(i_group * 3 + i);
+            }
+            let bits = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+            for i in 0..4 {
+                if i <= group_len {
+                    s.push(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+                } else {
+                    s.push('=');
+                }
+            }
+        }
+        s
+    }
+
+    /// Constructor from `"<bit length>:<base64>"` made by [to_base64()](#method.to_base64).
+    ///
+    /// Base64 must use the standard alphabet, be padded with '=' and have _0_ in its unused bits, as RFC 4648 requires.
+    ///
+    /// # Errors
+    /// - `FidError::InvalidBitLength` when `s` does not start with a decimal `u64` followed by ':'.
+    /// - `FidError::ZeroLength` when the bit length is _0_.
+    /// - `FidError::EncodedLengthMismatch` when the number of base64 characters is not _4 * ceil(ceil(bit length / 8) / 3)_.
+    /// - `FidError::InvalidCharacter` when a character is not in the alphabet, '=' is misplaced,
+    ///   or the last character before '=' has unused bits of _1_.
+    /// - `FidError::NonZeroPaddingBits` when a bit after the bit length is _1_.
+    pub fn from_base64(s: &str) -> Result<Self, FidError> {
+        let (bit_len, payload, payload_offset) = split_bit_len(s)?;
+        let byte_len = bit_len.div_ceil(8);
+        check_encoded_len(payload, byte_len.div_ceil(3) * 4)?;
+
+        let data_len = (byte_len * 8).div_ceil(6) as usize;
+        let mut byte_vec = Vec::with_capacity(byte_len as usize);
+        let (mut acc, mut acc_len) = (0u32, 0);
+        for (i, &byte) in payload.as_bytes().iter().enumerate() {
+            let invalid = FidError::InvalidCharacter {
+                byte,
+                offset: (payload_offset + i) as u64,
+            };
+            if i >= data_len {
+                if byte != b'=' {
+                    return Err(invalid);
+                }
+                continue;
+            }
+            let Some(value) = base64_value(byte) else {
+                return Err(invalid);
+            };
+            acc = acc << 6 | value as u32;
+            acc_len += 6;
+            if acc_len >= 8 {
+                acc_len -= 8;
+                byte_vec.push((acc >> acc_len) as u8);
+                acc &= (1 << acc_len) - 1;
+            }
+            if i == data_len - 1 && acc != 0 {
+                return Err(invalid);
+            }
+        }
+        Fid::try_from_raw_parts(byte_vec, bit_len, BitOrder::MsbFirst)
+    }
+
+    /// Returns `"<bit length>:"` with capacity for `payload_len` more characters.
+    fn encoding_prefix(&self, payload_len: usize) -> String {
+        let mut s = String::with_capacity(21 + payload_len);
+        // Writing to `String` never fails.
+        write!(s, "{}:", self.bit_len).unwrap();
+        s
+    }
+}
+
+/// Splits `"<bit length>:<payload>"` into the bit length, the payload and the offset of the payload in `s`.
+fn split_bit_len(s: &str) -> Result<(u64, &str, usize), FidError> {
+    let (digits, payload) = s.split_once(':').ok_or(FidError::InvalidBitLength)?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(FidError::InvalidBitLength);
+    }
+    let bit_len: u64 = digits.parse().map_err(|_| FidError::InvalidBitLength)?;
+    if bit_len == 0 {
+        return Err(FidError::ZeroLength);
+    }
+    Ok((bit_len, payload, digits.len() + 1))
+}
+
+fn check_encoded_len(payload: &str, expected: u64) -> Result<(), FidError> {
+    if payload.len() as u64 != expected {
+        return Err(FidError::EncodedLengthMismatch {
+            expected,
+            actual: payload.len() as u64,
+        });
+    }
+    Ok(())
+}
+
+fn base64_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'A'..=b'Z' => Some(byte - b'A'),
+        b'a'..=b'z' => Some(byte - b'a' + 26),
+        b'0'..=b'9' => Some(byte - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod hex_success_tests {
+    use crate::{BitOrder, Fid};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_fid, expected_hex): (Fid, &str) = $value;
+                assert_eq!(in_fid.to_hex(), expected_hex);
+                assert_eq!(Fid::from_hex(expected_hex), Ok(in_fid.clone()));
+                assert_eq!(Fid::from_hex(&expected_hex.to_uppercase()), Ok(in_fid));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (Fid::from("0"), "1:00"),
+        t2: (Fid::from("1"), "1:80"),
+        t3: (Fid::from("0100_1"), "5:48"),
+        t4: (Fid::from("11111111"), "8:ff"),
+        t5: (Fid::from("01001000_01"), "10:4840"),
+        t6: (Fid::from_raw_parts(vec![0b0001_0010, 0xff], 13, BitOrder::LsbFirst), "13:48f8"),
+        t7: (Fid::from(&[true; 100][..]), "100:fffffffffffffffffffffffff0"),
+    }
+}
+
+#[cfg(test)]
+mod hex_failure_tests {
+    use crate::{Fid, FidError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_hex, expected_error): (&str, FidError) = $value;
+                assert_eq!(Fid::from_hex(in_hex), Err(expected_error));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("48", FidError::InvalidBitLength),
+        t2: (":48", FidError::InvalidBitLength),
+        t3: ("+5:48", FidError::InvalidBitLength),
+        t4: ("5 :48", FidError::InvalidBitLength),
+        t5: ("18446744073709551616:48", FidError::InvalidBitLength),
+        t6: ("0:", FidError::ZeroLength),
+        t7: ("5:", FidError::EncodedLengthMismatch { expected: 2, actual: 0 }),
+        t8: ("5:4", FidError::EncodedLengthMismatch { expected: 2, actual: 1 }),
+        t9: ("5:4800", FidError::EncodedLengthMismatch { expected: 2, actual: 4 }),
+        t10: ("10:48g0", FidError::InvalidCharacter { byte: b'g', offset: 5 }),
+        t11: ("10:48 0", FidError::InvalidCharacter { byte: b' ', offset: 5 }),
+        t12: ("5:4c", FidError::NonZeroPaddingBits { bit_len: 5 }),
+        t13: ("10:4841", FidError::NonZeroPaddingBits { bit_len: 10 }),
+    }
+}
+
+#[cfg(test)]
+mod base64_success_tests {
+    use crate::{BitOrder, Fid};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_fid, expected_base64): (Fid, &str) = $value;
+                assert_eq!(in_fid.to_base64(), expected_base64);
+                assert_eq!(Fid::from_base64(expected_base64), Ok(in_fid));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (Fid::from("0"), "1:AA=="),
+        t2: (Fid::from("1"), "1:gA=="),
+        t3: (Fid::from("0100_1"), "5:SA=="),
+        t4: (Fid::from("01001000_01"), "10:SEA="),
+        t5: (Fid::from("01001000_01000000_1"), "17:SECA"),
+        t6: (Fid::from_raw_parts(vec![0b0001_0010, 0xff], 13, BitOrder::LsbFirst), "13:SPg="),
+        t7: (Fid::from(&[true; 24][..]), "24:////"),
+        t8: (Fid::from_raw_parts(vec![0xfb, 0xef, 0xbe, 0x00], 32, BitOrder::MsbFirst), "32:++++AA=="),
+    }
+}
+
+#[cfg(test)]
+mod base64_failure_tests {
+    use crate::{Fid, FidError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_base64, expected_error): (&str, FidError) = $value;
+                assert_eq!(Fid::from_base64(in_base64), Err(expected_error));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("SA==", FidError::InvalidBitLength),
+        t2: ("five:SA==", FidError::InvalidBitLength),
+        t3: ("0:", FidError::ZeroLength),
+        t4: ("5:SA", FidError::EncodedLengthMismatch { expected: 4, actual: 2 }),
+        t5: ("5:SA==SA==", FidError::EncodedLengthMismatch { expected: 4, actual: 8 }),
+        t6: ("10:S-A=", FidError::InvalidCharacter { byte: b'-', offset: 4 }),
+        t7: ("10:SE==", FidError::InvalidCharacter { byte: b'=', offset: 5 }),
+        t8: ("10:SEAA", FidError::InvalidCharacter { byte: b'A', offset: 6 }),
+        t9: ("5:SB==", FidError::InvalidCharacter { byte: b'B', offset: 3 }),
+        t10: ("10:SEF=", FidError::InvalidCharacter { byte: b'F', offset: 5 }),
+        t11: ("5:TA==", FidError::NonZeroPaddingBits { bit_len: 5 }),
+    }
+}
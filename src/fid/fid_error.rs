@@ -0,0 +1,61 @@
+use super::FidError;
+use core::fmt;
+
+impl fmt::Display for FidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FidError::ZeroLength => write!(f, "bit length must be greater than 0"),
+            FidError::PositionOutOfRange { position, len } => {
+                write!(f, "position {} out of range for length {}", position, len)
+            }
+            FidError::UnsortedPositions { previous, position } => write!(
+                f,
+                "positions must be in ascending order but {} follows {}",
+                position, previous
+            ),
+            FidError::InvalidRange { start, end, len } => {
+                write!(f, "range {}..{} is invalid for length {}", start, end, len)
+            }
+            FidError::LengthOverflow => write!(f, "bit length exceeds u64::MAX"),
+            FidError::InvalidCharacter { byte, offset } => write!(
+                f,
+                "unexpected character {:?} at byte {}",
+                char::from(*byte),
+                offset
+            ),
+            FidError::InvalidPosition { line } => write!(f, "line {}: invalid position", line),
+            FidError::TruncatedInput { expected, actual } => write!(
+                f,
+                "input ended after {} bytes while {} bytes are required",
+                actual, expected
+            ),
+            FidError::InvalidBitLength => {
+                write!(f, "expected a decimal bit length followed by ':'")
+            }
+            FidError::EncodedLengthMismatch { expected, actual } => write!(
+                f,
+                "expected {} characters after bit length but got {}",
+                expected, actual
+            ),
+            FidError::NonZeroPaddingBits { bit_len } => {
+                write!(f, "bits after bit length {} must be 0", bit_len)
+            }
+            FidError::Corrupted { reason } => write!(f, "corrupted input: {}", reason),
+            #[cfg(feature = "std")]
+            FidError::Io { message, .. } => write!(f, "I/O error: {}", message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FidError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for FidError {
+    fn from(e: std::io::Error) -> Self {
+        FidError::Io {
+            kind: e.kind(),
+            message: e.to_string(),
+        }
+    }
+}
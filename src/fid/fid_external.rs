@@ -0,0 +1,460 @@
+use super::fid_io::{for_each_chunk, for_each_text_bit, PositionsParser};
+use super::mapped_fid::{HEADER_LEN, MAGIC, VERSION};
+use super::{BitOrder, ExternalBuilder, FidError, MappedFidLayout, ReadFormat};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+/// Default of [ExternalBuilder::buffer_size()](struct.ExternalBuilder.html#method.buffer_size).
+const DEFAULT_BUFFER_SIZE: usize = 1 << 20;
+
+/// Buffered writer to a section of the output starting at `offset`.
+///
+/// `buf` never grows beyond `buffer_size`.
+struct Section {
+    offset: u64,
+    buffer_size: usize,
+    buf: Vec<u8>,
+}
+
+impl Section {
+    fn new(offset: u64, buffer_size: usize) -> Self {
+        Section {
+            offset,
+            buffer_size,
+            buf: Vec::with_capacity(buffer_size),
+        }
+    }
+
+    /// Writes `bytes`, split at the boundary of the buffer so that the buffer is flushed whenever it gets full.
+    fn write<W: Write + Seek>(&mut self, out: &mut W, mut bytes: &[u8]) -> io::Result<()> {
+        while !bytes.is_empty() {
+            let len = bytes.len().min(self.buffer_size - self.buf.len());
+            self.buf.extend_from_slice(&bytes[..len]);
+            bytes = &bytes[len..];
+            if self.buf.len() == self.buffer_size {
+                self.flush(out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush<W: Write + Seek>(&mut self, out: &mut W) -> io::Result<()> {
+        if !self.buf.is_empty() {
+            out.seek(SeekFrom::Start(self.offset))?;
+            out.write_all(&self.buf)?;
+            self.offset += self.buf.len() as u64;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+}
+
+/// Writer of a FID file, which computes the rank directory as bits are pushed.
+struct FidFileWriter<W> {
+    out: W,
+    layout: MappedFidLayout,
+    bits: Section,
+    chunks: Section,
+    blocks: Section,
+
+    /// Number of bits pushed so far.
+    pushed: u64,
+    pos_in_chunk: u64,
+    pos_in_block: u64,
+    rank_in_chunk: u64,
+    rank: u64,
+}
+
+impl<W: Write + Seek> FidFileWriter<W> {
+    fn new(mut out: W, bit_len: u64, buffer_size: usize) -> Result<Self, FidError> {
+        let layout = MappedFidLayout::new(bit_len);
+        out.seek(SeekFrom::Start(0))?;
+        out.write_all(&MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        out.write_all(&0u32.to_le_bytes())?;
+        out.write_all(&bit_len.to_le_bytes())?;
+        Ok(FidFileWriter {
+            out,
+            layout,
+            bits: Section::new(HEADER_LEN, buffer_size),
+            chunks: Section::new(layout.chunks_offset, buffer_size),
+            blocks: Section::new(layout.blocks_offset, buffer_size),
+            pushed: 0,
+            pos_in_chunk: 0,
+            pos_in_block: 0,
+            rank_in_chunk: 0,
+            rank: 0,
+        })
+    }
+
+    /// Pushes the first `len` bits (MSB-first) of `byte`. Only the last byte may have _`len` < 8_.
+    fn push_byte(&mut self, byte: u8, len: u64) -> Result<(), FidError> {
+        if self.pushed + len > self.layout.bit_len {
+            return Err(FidError::Corrupted {
+                reason: "input changed while building",
+            });
+        }
+        let byte = byte & !0xffu8.checked_shr(len as u32).unwrap_or(0);
+        self.bits.write(&mut self.out, &[byte])?;
+        self.pushed += len;
+
+        let mut word = (byte as u64) << 56;
+        let mut rest = len;
+        while rest > 0 {
+            let take = rest
+                .min(self.layout.block_size - self.pos_in_block)
+                .min(self.layout.chunk_size - self.pos_in_chunk);
+            self.rank_in_chunk += (word >> (64 - take)).count_ones() as u64;
+            word <<= take;
+            rest -= take;
+            self.pos_in_block += take;
+            self.pos_in_chunk += take;
+
+            if self.pos_in_block == self.layout.block_size
+                || self.pos_in_chunk == self.layout.chunk_size
+            {
+                self.end_block()?;
+            }
+            if self.pos_in_chunk == self.layout.chunk_size {
+                self.end_chunk()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn end_block(&mut self) -> io::Result<()> {
+        self.pos_in_block = 0;
+        let value = self.rank_in_chunk as u16;
+        self.blocks.write(&mut self.out, &value.to_le_bytes())
+    }
+
+    fn end_chunk(&mut self) -> io::Result<()> {
+        self.pos_in_chunk = 0;
+        self.rank += self.rank_in_chunk;
+        self.rank_in_chunk = 0;
+        self.chunks.write(&mut self.out, &self.rank.to_le_bytes())
+    }
+
+    /// Writes the last block, chunk and padding, and flushes everything.
+    fn finish(mut self) -> Result<(), FidError> {
+        if self.pushed != self.layout.bit_len {
+            return Err(FidError::Corrupted {
+                reason: "input changed while building",
+            });
+        }
+        if self.pos_in_block > 0 {
+            self.end_block()?;
+        }
+        if self.pos_in_chunk > 0 {
+            self.end_chunk()?;
+        }
+        let padding = (self.layout.chunks_offset - HEADER_LEN) - self.pushed.div_ceil(8);
+        self.bits
+            .write(&mut self.out, &[0; 8][..padding as usize])?;
+
+        self.bits.flush(&mut self.out)?;
+        self.chunks.flush(&mut self.out)?;
+        self.blocks.flush(&mut self.out)?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+impl ExternalBuilder {
+    /// Constructor with 1 MiB buffers.
+    pub fn new() -> Self {
+        ExternalBuilder {
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        }
+    }
+
+    /// Sets the size in bytes of each buffer: one for input and three for output sections (bits, chunks and blocks).
+    ///
+    /// # Panics
+    /// When _`buffer_size` == 0_.
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        assert!(buffer_size > 0);
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Reads `input` in `format` and writes a FID file to `output`.
+    ///
+    /// `ReadFormat::Text` input is read twice: first to count bits and then to write them,
+    /// since the rank directory layout depends on the bit length.
+    /// `ReadFormat::Positions` input must be in ascending order.
+    ///
+    /// # Errors
+    /// - Same as [Fid::from_reader()](struct.Fid.html#method.from_reader) for invalid `input`.
+    /// - `FidError::UnsortedPositions` when `ReadFormat::Positions` input is not in ascending order.
+    /// - `FidError::Corrupted` when `ReadFormat::Text` input changes between the two reads.
+    /// - `FidError::Io` when `input` or `output` fails.
+    pub fn build<R: Read + Seek, W: Write + Seek>(
+        &self,
+        mut input: R,
+        format: ReadFormat,
+        output: W,
+    ) -> Result<(), FidError> {
+        let mut buf = vec![0u8; self.buffer_size];
+        match format {
+            ReadFormat::Text => {
+                let start = input.stream_position()?;
+                let mut bit_len = 0;
+                for_each_text_bit(&mut input, &mut buf, |_| {
+                    bit_len += 1;
+                    Ok(())
+                })?;
+                if bit_len == 0 {
+                    return Err(FidError::ZeroLength);
+                }
+
+                input.seek(SeekFrom::Start(start))?;
+                let mut writer = FidFileWriter::new(output, bit_len, self.buffer_size)?;
+                let (mut byte, mut byte_len) = (0u8, 0);
+                for_each_text_bit(&mut input, &mut buf, |bit| {
+                    if bit {
+                        byte |= 0x80 >> byte_len;
+                    }
+                    byte_len += 1;
+                    if byte_len == 8 {
+                        writer.push_byte(byte, 8)?;
+                        (byte, byte_len) = (0, 0);
+                    }
+                    Ok(())
+                })?;
+                if byte_len > 0 {
+                    writer.push_byte(byte, byte_len)?;
+                }
+                writer.finish()
+            }
+            ReadFormat::Bytes { bit_len, bit_order } => {
+                if bit_len == 0 {
+                    return Err(FidError::ZeroLength);
+                }
+
+                let mut writer = FidFileWriter::new(output, bit_len, self.buffer_size)?;
+                let byte_len = bit_len.div_ceil(8);
+                let mut read = 0;
+                while read < byte_len {
+                    let want = (byte_len - read).min(buf.len() as u64) as usize;
+                    let n = match input.read(&mut buf[..want]) {
+                        Ok(0) => {
+                            return Err(FidError::TruncatedInput {
+                                expected: byte_len,
+                                actual: read,
+                            })
+                        }
+                        Ok(n) => n,
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                        Err(e) => return Err(e.into()),
+                    };
+                    for &byte in &buf[..n] {
+                        let byte = match bit_order {
+                            BitOrder::MsbFirst => byte,
+                            BitOrder::LsbFirst => byte.reverse_bits(),
+                        };
+                        writer.push_byte(byte, (bit_len - read * 8).min(8))?;
+                        read += 1;
+                    }
+                }
+                writer.finish()
+            }
+            ReadFormat::Positions { len } => {
+                if len == 0 {
+                    return Err(FidError::ZeroLength);
+                }
+
+                let mut writer = FidFileWriter::new(output, len, self.buffer_size)?;
+                let (mut byte, mut i_byte) = (0u8, 0u64);
+                let mut previous: Option<u64> = None;
+                let mut parser = PositionsParser::new(|position| {
+                    if position >= len {
+                        return Err(FidError::PositionOutOfRange { position, len });
+                    }
+                    if let Some(previous) = previous.filter(|&previous| previous > position) {
+                        return Err(FidError::UnsortedPositions { previous, position });
+                    }
+                    while i_byte < position / 8 {
+                        writer.push_byte(byte, 8)?;
+                        (byte, i_byte) = (0, i_byte + 1);
+                    }
+                    byte |= 0x80 >> (position % 8);
+                    previous = Some(position);
+                    Ok(())
+                });
+                for_each_chunk(&mut input, &mut buf, |chunk| {
+                    chunk.iter().try_for_each(|&c| parser.feed(c))
+                })?;
+                parser.end_line()?;
+
+                while i_byte < len.div_ceil(8) {
+                    writer.push_byte(byte, (len - i_byte * 8).min(8))?;
+                    (byte, i_byte) = (0, i_byte + 1);
+                }
+                writer.finish()
+            }
+        }
+    }
+}
+
+impl Default for ExternalBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod external_builder_success_tests {
+    use super::Section;
+    use crate::{BitOrder, ExternalBuilder, Fid, MappedFid, ReadFormat};
+    use std::io::Cursor;
+
+    fn sample_bits(len: usize) -> Vec<bool> {
+        (0..len)
+            .map(|i| i % 3 == 0 || i % 7 == 0 || (i / 100) % 5 == 1)
+            .collect()
+    }
+
+    fn build(input: &[u8], format: ReadFormat, buffer_size: usize) -> MappedFid<Vec<u8>> {
+        let mut file = Cursor::new(Vec::new());
+        ExternalBuilder::new()
+            .buffer_size(buffer_size)
+            .build(Cursor::new(input), format, &mut file)
+            .unwrap();
+        MappedFid::from_bytes(file.into_inner()).unwrap()
+    }
+
+    fn assert_same(mapped: &MappedFid<Vec<u8>>, fid: &Fid) {
+        assert_eq!(mapped.len(), fid.len());
+        assert_eq!(&mapped.to_fid(), fid);
+        for i in 0..fid.len() {
+            assert_eq!(mapped.get(i), fid[i], "i = {}", i);
+            assert_eq!(mapped.rank(i), fid.rank(i), "i = {}", i);
+            assert_eq!(mapped.rank0(i), fid.rank0(i), "i = {}", i);
+        }
+        for k in 0..=fid.len() {
+            assert_eq!(mapped.select1(k), fid.select1(k), "k = {}", k);
+            assert_eq!(mapped.select0(k), fid.select0(k), "k = {}", k);
+        }
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_len, in_buffer_size): (usize, usize) = $value;
+                let bits = sample_bits(in_len);
+                let fid = Fid::from(&bits[..]);
+
+                let text = format!("{:#}", fid);
+                assert_same(&build(text.as_bytes(), ReadFormat::Text, in_buffer_size), &fid);
+
+                let format = ReadFormat::Bytes {
+                    bit_len: in_len as u64,
+                    bit_order: BitOrder::MsbFirst,
+                };
+                assert_same(&build(fid.as_bytes(), format, in_buffer_size), &fid);
+
+                let lsb_first: Vec<u8> = fid.as_bytes().iter().map(|b| b.reverse_bits()).collect();
+                let format = ReadFormat::Bytes {
+                    bit_len: in_len as u64,
+                    bit_order: BitOrder::LsbFirst,
+                };
+                assert_same(&build(&lsb_first, format, in_buffer_size), &fid);
+
+                let positions: String = (0..in_len)
+                    .filter(|&i| bits[i])
+                    .map(|i| format!("{}\n", i))
+                    .collect();
+                let format = ReadFormat::Positions { len: in_len as u64 };
+                assert_same(&build(positions.as_bytes(), format, in_buffer_size), &fid);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (1, 1),
+        t2: (2, 1),
+        t3: (5, 1),
+        t4: (8, 3),
+        t5: (9, 1),
+        t6: (64, 7),
+        t7: (65, 1024),
+        t8: (1000, 1),
+        t9: (1000, 13),
+        t10: (4096, 100),
+        t11: (10_000, 1 << 20),
+    }
+
+    #[test]
+    fn section_buffer_never_grows() {
+        for buffer_size in [1, 3, 5, 8, 13] {
+            let mut out = Cursor::new(Vec::new());
+            let mut section = Section::new(2, buffer_size);
+            let capacity = section.buf.capacity();
+            let mut expected = vec![0u8; 2];
+            for i in 0..20u64 {
+                let bytes = (i * 0x0101_0101_0101_0101).to_le_bytes();
+                section.write(&mut out, &bytes[..(i % 9) as usize]).unwrap();
+                expected.extend_from_slice(&bytes[..(i % 9) as usize]);
+                assert_eq!(
+                    section.buf.capacity(),
+                    capacity,
+                    "buffer_size = {}",
+                    buffer_size
+                );
+                assert!(
+                    section.buf.len() < buffer_size,
+                    "buffer_size = {}",
+                    buffer_size
+                );
+            }
+            section.flush(&mut out).unwrap();
+            assert_eq!(out.into_inner(), expected, "buffer_size = {}", buffer_size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod external_builder_failure_tests {
+    use crate::{ExternalBuilder, FidError, ReadFormat};
+    use std::io::Cursor;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_input, in_format, expected_err): (&[u8], ReadFormat, FidError) = $value;
+                let result = ExternalBuilder::new().build(
+                    Cursor::new(in_input),
+                    in_format,
+                    Cursor::new(Vec::new()),
+                );
+                assert_eq!(result, Err(expected_err));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        text_empty: (b"\n", ReadFormat::Text, FidError::ZeroLength),
+        text_invalid: (b"01x", ReadFormat::Text, FidError::InvalidCharacter { byte: b'x', offset: 2 }),
+        bytes_truncated: (
+            &[0],
+            ReadFormat::Bytes { bit_len: 9, bit_order: crate::BitOrder::MsbFirst },
+            FidError::TruncatedInput { expected: 2, actual: 1 }
+        ),
+        positions_unsorted: (
+            b"3\n1\n",
+            ReadFormat::Positions { len: 5 },
+            FidError::UnsortedPositions { previous: 3, position: 1 }
+        ),
+        positions_out_of_range: (
+            b"3\n5\n",
+            ReadFormat::Positions { len: 5 },
+            FidError::PositionOutOfRange { position: 5, len: 5 }
+        ),
+    }
+}
@@ -0,0 +1,217 @@
+use super::Fid;
+use core::fmt::{self, Write};
+
+/// Number of bits `Debug` shows.
+const DEBUG_PREVIEW_LEN: u64 = 64;
+
+impl Fid {
+    /// Writes the first `len` bits as '0' and '1', with '_' after every 8 bits if `grouped`.
+    fn write_bits(&self, f: &mut fmt::Formatter, len: u64, grouped: bool) -> fmt::Result {
+        let mut buf = [0u8; 9];
+        for i_byte in 0..self.byte_vec.len() {
+            let byte = self.msb_first_byte(i_byte);
+            let pos = i_byte as u64 * 8;
+            if pos >= len {
+                break;
+            }
+            let byte_len = (len - pos).min(8) as usize;
+
+            let mut buf_len = 0;
+            if grouped && i_byte > 0 {
+                buf[0] = b'_';
+                buf_len = 1;
+            }
+            for i in 0..byte_len {
+                buf[buf_len] = if byte & (0x80 >> i) != 0 { b'1' } else { b'0' };
+                buf_len += 1;
+            }
+            // Only ASCII characters are written.
+            f.write_str(core::str::from_utf8(&buf[..buf_len]).unwrap())?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes bits as '0' and '1', which `Fid::from::<&str>()` parses back.
+///
+/// The alternate flag (`{:#}`) inserts '_' after every 8 bits, which `Fid::from::<&str>()` ignores.
+///
+/// # Examples
+/// ```
+/// use fid_rs::Fid;
+///
+/// let fid = Fid::from("01001000_01");
+/// assert_eq!(format!("{}", fid), "0100100001");
+/// assert_eq!(format!("{:#}", fid), "01001000_01");
+/// assert_eq!(Fid::from(format!("{:#}", fid).as_str()), fid);
+/// ```
+///
+/// Width, fill and alignment are applied as for `str` (left-aligned by default). Precision is ignored.
+///
+/// ```
+/// use fid_rs::Fid;
+///
+/// let fid = Fid::from("0100_1");
+/// assert_eq!(format!("{:8}|", fid), "01001   |");
+/// assert_eq!(format!("{:*>8}", fid), "***01001");
+/// ```
+impl fmt::Display for Fid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let grouped = f.alternate();
+        let width = match f.width() {
+            Some(width) => width as u64,
+            None => return self.write_bits(f, self.bit_len, grouped),
+        };
+
+        let underscores = if grouped {
+            self.bit_len.saturating_sub(1) / 8
+        } else {
+            0
+        };
+        let padding = width.saturating_sub(self.bit_len + underscores);
+        let (pre, post) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(fmt::Alignment::Left) | None => (0, padding),
+        };
+
+        let fill = f.fill();
+        for _ in 0..pre {
+            f.write_char(fill)?;
+        }
+        self.write_bits(f, self.bit_len, grouped)?;
+        for _ in 0..post {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+/// Same as `Display`.
+///
+/// Unlike integers, the alternate flag (`{:#b}`) does not prefix `0b` but inserts '_' after every 8 bits,
+/// so that the output round-trips through `Fid::from::<&str>()`.
+impl fmt::Binary for Fid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Shows the length, popcount and first 64 bits.
+///
+/// ```
+/// use fid_rs::Fid;
+///
+/// let fid = Fid::from("0100_1");
+/// assert_eq!(format!("{:?}", fid), "Fid { len: 5, popcount: 2, bits: 01001 }");
+/// ```
+impl fmt::Debug for Fid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        struct Preview<'a>(&'a Fid);
+
+        impl fmt::Debug for Preview<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let fid = self.0;
+                fid.write_bits(f, fid.bit_len.min(DEBUG_PREVIEW_LEN), f.alternate())?;
+                if fid.bit_len > DEBUG_PREVIEW_LEN {
+                    f.write_str("...")?;
+                }
+                Ok(())
+            }
+        }
+
+        let popcount = if self.bit_len == 0 {
+            0
+        } else {
+            self.rank(self.bit_len - 1)
+        };
+        f.debug_struct("Fid")
+            .field("len", &self.bit_len)
+            .field("popcount", &popcount)
+            .field("bits", &Preview(self))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod display_success_tests {
+    use crate::Fid;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_fid_str, expected_display, expected_alternate) = $value;
+                let fid = Fid::from(in_fid_str);
+                assert_eq!(format!("{}", fid), expected_display);
+                assert_eq!(format!("{:b}", fid), expected_display);
+                assert_eq!(format!("{:#}", fid), expected_alternate);
+                assert_eq!(format!("{:#b}", fid), expected_alternate);
+                assert_eq!(Fid::from(expected_display), fid);
+                assert_eq!(Fid::from(expected_alternate), fid);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: ("0", "0", "0"),
+        t2: ("1", "1", "1"),
+        t3: ("0100_1", "01001", "01001"),
+        t4: ("01001000", "01001000", "01001000"),
+        t5: ("0_1001000_1", "010010001", "01001000_1"),
+        t6: (
+            "11110000_00001111_10101010_1",
+            "1111000000001111101010101",
+            "11110000_00001111_10101010_1",
+        ),
+    }
+
+    #[test]
+    fn padding() {
+        let fid = Fid::from("0100_1");
+        assert_eq!(format!("{:>20}", fid), format!("{:>20}", "01001"));
+        assert_eq!(format!("{:<8}|", fid), "01001   |");
+        assert_eq!(format!("{:-^9}", fid), "--01001--");
+        assert_eq!(format!("{:*>8b}", fid), "***01001");
+        assert_eq!(format!("{:3}", fid), "01001");
+
+        let fid = Fid::from("01001000_01");
+        assert_eq!(format!("{:#>13}", fid), "###0100100001");
+        assert_eq!(format!("{:>#13}", fid), "  01001000_01");
+    }
+}
+
+#[cfg(test)]
+mod debug_success_tests {
+    use crate::Fid;
+
+    #[test]
+    fn short() {
+        let fid = Fid::from("0100_1");
+        assert_eq!(
+            format!("{:?}", fid),
+            "Fid { len: 5, popcount: 2, bits: 01001 }"
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let fid = Fid::from(&[true; 100][..]);
+        assert_eq!(
+            format!("{:?}", fid),
+            format!(
+                "Fid {{ len: 100, popcount: 100, bits: {}... }}",
+                "1".repeat(64)
+            )
+        );
+    }
+
+    #[test]
+    fn ignores_padding_bits() {
+        let mut fid = Fid::from("0100_1");
+        *fid.byte_vec.last_mut().unwrap() |= 0b0000_0111;
+        assert_eq!(format!("{}", fid), "01001");
+    }
+}
@@ -1,7 +1,8 @@
-use super::{Blocks, Chunks, Fid};
+use super::{BitOrder, Chunks, Fid, FidError};
 use crate::internal_data_structure::popcount_table::PopcountTable;
 use crate::internal_data_structure::raw_bit_vector::RawBitVector;
-use std::ops::Index;
+use alloc::vec::Vec;
+use core::ops::Index;
 
 impl From<&str> for Fid {
     /// Constructor from string representation of bit sequence.
@@ -62,18 +63,14 @@ impl From<&[bool]> for Fid {
         assert!(!bits.is_empty());
 
         let mut byte_vec: Vec<u8> = Vec::with_capacity(bits.len() / 8 + 1);
-        let mut last_byte_len = 0u8;
 
         for bits8 in bits.chunks(8) {
-            last_byte_len = bits8.len() as u8; // although this bits8 might not be a last byte.
-
-            let byte = (0..last_byte_len).fold(0, |byte, i| {
-                byte + if bits8[i as usize] { 1 << (7 - i) } else { 0 }
-            });
+            let byte =
+                (0..bits8.len()).fold(0, |byte, i| byte + if bits8[i] { 1 << (7 - i) } else { 0 });
             byte_vec.push(byte);
         }
 
-        Fid::build(byte_vec, last_byte_len)
+        Fid::from_raw_parts(byte_vec, bits.len() as u64, BitOrder::MsbFirst)
     }
 }
 
@@ -98,19 +95,120 @@ impl Index<u64> for Fid {
 
 impl Fid {
     /// Build FID from byte vector.
-    fn build(byte_vec: Vec<u8>, last_byte_len: u8) -> Self {
+    fn build(byte_vec: Vec<u8>, last_byte_len: u8, bit_order: BitOrder) -> Self {
         let bit_len = (byte_vec.len() - 1) as u64 * 8 + last_byte_len as u64;
-        let rbv = RawBitVector::new(&byte_vec[..], 0, last_byte_len);
+        let rbv = RawBitVector::new(&byte_vec[..], 0, last_byte_len).with_bit_order(bit_order);
         let chunks = Chunks::new(&rbv);
-        let table = PopcountTable::new(Blocks::calc_block_size(rbv.len()));
+        let table = PopcountTable::new(Chunks::calc_block_size(rbv.len()));
         Self {
             byte_vec,
             bit_len,
+            bit_order,
             chunks,
             table,
         }
     }
 
+    /// Constructor from packed bits and bit length, without reordering bits.
+    /// Unused bits in the last byte are cleared.
+    ///
+    /// Inverse of [into_raw_parts()](#method.into_raw_parts).
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::{BitOrder, Fid};
+    ///
+    /// let msb_first = Fid::from_raw_parts(vec![0b0100_1000], 5, BitOrder::MsbFirst);
+    /// let lsb_first = Fid::from_raw_parts(vec![0b0001_0010], 5, BitOrder::LsbFirst);
+    /// assert_eq!(msb_first, Fid::from("0100_1"));
+    /// assert_eq!(lsb_first, Fid::from("0100_1"));
+    /// ```
+    ///
+    /// # Panics
+    /// When:
+    /// - _`bit_len` == 0_.
+    /// - _`byte_vec.len()` != ceil(`bit_len` / 8)_.
+    pub fn from_raw_parts(mut byte_vec: Vec<u8>, bit_len: u64, bit_order: BitOrder) -> Self {
+        assert!(bit_len > 0);
+        assert_eq!(byte_vec.len() as u64, bit_len.div_ceil(8));
+
+        let last_byte_len = (bit_len - 1) % 8 + 1;
+        if let Some(last_byte) = byte_vec.last_mut() {
+            let unused_mask = 0xffu8.checked_shr(last_byte_len as u32).unwrap_or(0);
+            *last_byte &= match bit_order {
+                BitOrder::MsbFirst => !unused_mask,
+                BitOrder::LsbFirst => !unused_mask.reverse_bits(),
+            };
+        }
+        Fid::build(byte_vec, last_byte_len as u8, bit_order)
+    }
+
+    /// Same as [from_raw_parts()](#method.from_raw_parts) but returns an error instead of panicking,
+    /// and rejects _1_ in unused bits in the last byte instead of clearing them.
+    ///
+    /// # Errors
+    /// - `FidError::ZeroLength` when _`bit_len` == 0_.
+    /// - `FidError::Corrupted` when _`byte_vec.len()` != ceil(`bit_len` / 8)_.
+    /// - `FidError::NonZeroPaddingBits` when any unused bit is _1_.
+    pub(super) fn try_from_raw_parts(
+        byte_vec: Vec<u8>,
+        bit_len: u64,
+        bit_order: BitOrder,
+    ) -> Result<Self, FidError> {
+        check_raw_parts(&byte_vec, bit_len, bit_order)?;
+        let last_byte_len = (bit_len - 1) % 8 + 1;
+        Ok(Fid::build(byte_vec, last_byte_len as u8, bit_order))
+    }
+
+    /// Returns the order of bits in [as_bytes()](#method.as_bytes).
+    pub fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
+    /// Returns `i`-th byte of `byte_vec` in `BitOrder::MsbFirst`.
+    pub(super) fn msb_first_byte(&self, i: usize) -> u8 {
+        let byte = self.byte_vec[i];
+        match self.bit_order {
+            BitOrder::MsbFirst => byte,
+            BitOrder::LsbFirst => byte.reverse_bits(),
+        }
+    }
+
+    /// Returns the bit length as 8-byte little endian integer, followed by packed bytes (MSB-first).
+    ///
+    /// This is the serialized format of language bindings.
+    #[cfg(any(feature = "ffi", feature = "wasm"))]
+    pub(crate) fn to_length_prefixed_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(8 + self.byte_vec.len());
+        buf.extend_from_slice(&self.bit_len.to_le_bytes());
+        buf.extend((0..self.byte_vec.len()).map(|i| self.msb_first_byte(i)));
+        buf
+    }
+
+    /// Inverse of `to_length_prefixed_bytes()`.
+    ///
+    /// Returns `None` when the bit length is 0 or inconsistent with the number of bytes.
+    #[cfg(any(feature = "ffi", feature = "wasm"))]
+    pub(crate) fn from_length_prefixed_bytes(buf: &[u8]) -> Option<Self> {
+        if buf.len() < 8 {
+            return None;
+        }
+        let (header, bytes) = buf.split_at(8);
+        let mut bit_len = [0u8; 8];
+        bit_len.copy_from_slice(header);
+        let bit_len = u64::from_le_bytes(bit_len);
+
+        if bit_len == 0 || bit_len.div_ceil(8) != bytes.len() as u64 {
+            None
+        } else {
+            Some(Fid::from_raw_parts(
+                bytes.to_vec(),
+                bit_len,
+                BitOrder::MsbFirst,
+            ))
+        }
+    }
+
     /// Returns the number of _1_ in _[0, `i`]_ elements of the `Fid`.
     ///
     /// # Panics
@@ -135,59 +233,20 @@ impl Fid {
     /// 1. Find `i_chunk`. _`i_chunk` = `i` / `chunk_size`_.
     /// 2. Get _`chunk_left` = Chunks[`i_chunk` - 1]_ only if _`i_chunk` > 0_.
     /// 3. Get _rank from chunk_left_ if `chunk_left` exists.
-    /// 4. Get _`chunk_right` = Chunks[`i_chunk`]_.
+    /// 4. Blocks of `chunk_right` (_`i_chunk`_-th chunk) start from _Blocks[`i_chunk` * `blocks_per_chunk`]_.
     /// 5. Find `i_block`. _`i_block` = (`i` - `i_chunk` * `chunk_size`) / block size_.
-    /// 6. Get _`block_left` = `chunk_right.blocks`[ `i_block` - 1]`_ only if _`i_block` > 0_.
+    /// 6. Get _`block_left` = Blocks[`i_chunk` * `blocks_per_chunk` + `i_block` - 1]_ only if _`i_block` > 0_.
     /// 7. Get _rank from block_left_ if `block_left` exists.
-    /// 8. Get inner-block data _`block_bits`. `block_bits` must be of _block size_ length, fulfilled with _0_ in right bits.
+    /// 8. Get inner-block data _`block_bits`_, bits of `block_right` up to `i`.
     /// 9. Calculate _rank of `block_bits`_ in _O(1)_ using a table memonizing _block size_ bit's popcount.
     pub fn rank(&self, i: u64) -> u64 {
-        let n = self.len();
-        assert!(i < n);
-        let chunk_size = Chunks::calc_chunk_size(n);
-        let block_size = Blocks::calc_block_size(n);
-
-        // 1.
-        let i_chunk = i / chunk_size as u64;
-
-        // 3.
-        let rank_from_chunk = if i_chunk == 0 {
-            0
-        } else {
-            // 2., 3.
-            let chunk_left = self.chunks.access(i_chunk - 1);
-            chunk_left.value()
-        };
-
-        // 4.
-        let chunk_right = self.chunks.access(i_chunk);
-
-        // 5.
-        let i_block = (i - i_chunk * chunk_size as u64) / block_size as u64;
-
-        // 7.
-        let rank_from_block = if i_block == 0 {
-            0
-        } else {
-            // 6., 7.
-            let block_left = chunk_right.blocks.access(i_block - 1);
-            block_left.value()
-        };
-
-        // 8.
-        let block_right = chunk_right.blocks.access(i_block);
-        let pos_block_start = i_chunk * chunk_size as u64 + i_block * block_size as u64;
-        assert!(i - pos_block_start < block_right.length() as u64);
-        let block_right_rbv = self
-            .rbv()
-            .clone_sub(pos_block_start, block_right.length() as u64);
-        let block_right_as_u32 = block_right_rbv.as_u32();
-        let bits_to_use = i - pos_block_start + 1;
-        let block_bits = block_right_as_u32 >> (32 - bits_to_use);
-        let rank_from_table = self.table.popcount(block_bits as u64);
-
-        // 9.
-        rank_from_chunk + rank_from_block as u64 + rank_from_table as u64
+        rank_by(
+            &self.rbv(),
+            i,
+            |i_chunk| self.chunks.chunk(i_chunk),
+            |i_block| self.chunks.block(i_block),
+            |block_bits| self.table.popcount(block_bits),
+        )
     }
 
     /// Returns the number of _0_ in _[0, `i`]_ elements of the `Fid`.
@@ -198,63 +257,61 @@ impl Fid {
         (i + 1) - self.rank(i)
     }
 
-    /// Returns the minimum position (0-origin) `i` where _`rank(i)` == num_ of `num`-th _1_ if exists. Else returns None.
+    /// Returns the position (0-origin) of the `k`-th (0-origin) _1_ if exists. Else returns None.
     ///
-    /// # Panics
-    /// When _`num` > length of the `Fid`_.
+    /// When it returns _Some(`i`)_, _`rank(i)` == `k` + 1_ and _`self[i]` == true_ hold.
     ///
     /// # Implementation detail
     /// Binary search using `rank()`.
-    pub fn select(&self, num: u64) -> Option<u64> {
-        let n = self.len();
-        assert!(num <= n);
+    pub fn select1(&self, k: u64) -> Option<u64> {
+        select_by(self.len(), k, |i| self.rank(i))
+    }
 
-        if num == 0 || num == 1 && self[0] {
-            return Some(0);
-        }
-        if self.rank(n - 1) < num {
-            return None;
-        };
-
-        let mut ng = 0;
-        let mut ok = n - 1;
-        while ok - ng > 1 {
-            let mid = (ok + ng) / 2;
-            if self.rank(mid) >= num {
-                ok = mid;
-            } else {
-                ng = mid;
-            }
-        }
-        Some(ok)
+    /// Returns the position (0-origin) of the `k`-th (0-origin) _0_ if exists. Else returns None.
+    ///
+    /// When it returns _Some(`i`)_, _`rank0(i)` == `k` + 1_ and _`self[i]` == false_ hold.
+    ///
+    /// # Implementation detail
+    /// Binary search using `rank0()`.
+    pub fn select0(&self, k: u64) -> Option<u64> {
+        select_by(self.len(), k, |i| self.rank0(i))
     }
 
-    /// Returns the minimum position (0-origin) `i` where _`rank(i)` == num_ of `num`-th _0_ if exists. Else returns None.
+    /// Returns the minimum position (0-origin) `i` where _`rank(i)` == num_ of `num`-th _1_ if exists. Else returns None.
+    ///
+    /// `select(0)` is always _Some(0)_, and _`select(num)` == `select1(num - 1)`_ for _`num` > 0_.
     ///
     /// # Panics
     /// When _`num` > length of the `Fid`_.
-    pub fn select0(&self, num: u64) -> Option<u64> {
-        let n = self.bit_len;
-        assert!(num <= n);
-
-        if num == 0 || num == 1 && !self[0] {
-            return Some(0);
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `select1(k)`, which takes 0-origin `k` and returns `None` instead of panicking"
+    )]
+    pub fn select(&self, num: u64) -> Option<u64> {
+        assert!(num <= self.len());
+        match num {
+            0 => Some(0),
+            _ => self.select1(num - 1),
         }
-        if self.rank0(n - 1) < num {
-            return None;
-        };
-
-        let mut ng = 0;
-        let mut ok = n - 1;
-        while ok - ng > 1 {
-            let mid = (ok + ng) / 2;
-            if self.rank0(mid) >= num {
-                ok = mid;
-            } else {
-                ng = mid;
-            }
+    }
+
+    /// Returns the minimum position (0-origin) `i` where _`rank0(i)` == num_ of `num`-th _0_ if exists. Else returns None.
+    ///
+    /// This is the behaviour of `select0()` until v0.2.
+    /// `select0_legacy(0)` is always _Some(0)_, and _`select0_legacy(num)` == `select0(num - 1)`_ for _`num` > 0_.
+    ///
+    /// # Panics
+    /// When _`num` > length of the `Fid`_.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `select0(k)`, which takes 0-origin `k` and returns `None` instead of panicking"
+    )]
+    pub fn select0_legacy(&self, num: u64) -> Option<u64> {
+        assert!(num <= self.len());
+        match num {
+            0 => Some(0),
+            _ => self.select0(num - 1),
         }
-        Some(ok)
     }
 
     /// Returns bit length of this FID.
@@ -267,7 +324,7 @@ impl Fid {
         self.bit_len == 0
     }
 
-    fn rbv(&self) -> RawBitVector {
+    fn rbv(&self) -> RawBitVector<'_> {
         let last_byte_len_or_0 = (self.bit_len % 8) as u8;
         RawBitVector::new(
             &self.byte_vec[..],
@@ -278,9 +335,147 @@ impl Fid {
                 last_byte_len_or_0
             },
         )
+        .with_bit_order(self.bit_order)
     }
 }
 
+/// Returns the number of _1_ in _[0, `i`]_ of `rbv` by the directory: `chunk_fn(i)` and `block_fn(i)` return
+/// the value of `i`-th chunk and block, and `table_fn(bits)` returns the popcount of block bits.
+///
+/// See [Fid::rank()](struct.Fid.html#method.rank) for the steps.
+///
+/// # Panics
+/// When _`i` >= `rbv.len()`_.
+pub(super) fn rank_by(
+    rbv: &RawBitVector,
+    i: u64,
+    chunk_fn: impl Fn(u64) -> u64,
+    block_fn: impl Fn(u64) -> u16,
+    table_fn: impl Fn(u64) -> u8,
+) -> u64 {
+    let n = rbv.len();
+    assert!(i < n);
+    let chunk_size = Chunks::calc_chunk_size(n);
+    let block_size = Chunks::calc_block_size(n);
+
+    // 1.
+    let i_chunk = i / chunk_size as u64;
+
+    // 3.
+    let rank_from_chunk = if i_chunk == 0 {
+        0
+    } else {
+        // 2., 3.
+        chunk_fn(i_chunk - 1)
+    };
+
+    // 4.
+    let first_block_of_chunk_right = i_chunk * Chunks::calc_blocks_per_chunk(n);
+
+    // 5.
+    let i_block = (i - i_chunk * chunk_size as u64) / block_size as u64;
+
+    // 7.
+    let rank_from_block = if i_block == 0 {
+        0
+    } else {
+        // 6., 7.
+        block_fn(first_block_of_chunk_right + i_block - 1)
+    };
+
+    // 8.
+    let pos_block_start = i_chunk * chunk_size as u64 + i_block * block_size as u64;
+    let bits_to_use = i - pos_block_start + 1;
+    assert!(bits_to_use <= block_size as u64);
+    let block_bits = rbv.clone_sub(pos_block_start, bits_to_use).as_u32() >> (32 - bits_to_use);
+    let rank_from_table = table_fn(block_bits as u64);
+
+    // 9.
+    rank_from_chunk + rank_from_block as u64 + rank_from_table as u64
+}
+
+/// Returns the minimum `i` in _[0, `n`)_ where _`rank_fn(i)` > `k`_ if exists.
+///
+/// `rank_fn` must be monotonically non-decreasing and _`rank_fn(i)` <= `rank_fn(i - 1)` + 1_.
+pub(super) fn select_by(n: u64, k: u64, rank_fn: impl Fn(u64) -> u64) -> Option<u64> {
+    if k >= rank_fn(n - 1) {
+        return None;
+    }
+
+    // rank_fn(ng) <= k < rank_fn(ok)
+    if rank_fn(0) > k {
+        return Some(0);
+    }
+    let mut ng = 0;
+    let mut ok = n - 1;
+    while ok - ng > 1 {
+        let mid = (ok + ng) / 2;
+        if rank_fn(mid) > k {
+            ok = mid;
+        } else {
+            ng = mid;
+        }
+    }
+    Some(ok)
+}
+
+/// Checks that packed bits `byte_vec` have _ceil(`bit_len` / 8)_ bytes and _0_ in unused bits.
+pub(super) fn check_raw_parts(
+    byte_vec: &[u8],
+    bit_len: u64,
+    bit_order: BitOrder,
+) -> Result<(), FidError> {
+    if bit_len == 0 {
+        return Err(FidError::ZeroLength);
+    }
+    if byte_vec.len() as u64 != bit_len.div_ceil(8) {
+        return Err(FidError::Corrupted {
+            reason: "byte length inconsistent with bit length",
+        });
+    }
+    let last_byte_len = (bit_len - 1) % 8 + 1;
+    let unused_mask = 0xffu8.checked_shr(last_byte_len as u32).unwrap_or(0);
+    let unused_mask = match bit_order {
+        BitOrder::MsbFirst => unused_mask,
+        BitOrder::LsbFirst => unused_mask.reverse_bits(),
+    };
+    if byte_vec[byte_vec.len() - 1] & unused_mask != 0 {
+        return Err(FidError::NonZeroPaddingBits { bit_len });
+    }
+    Ok(())
+}
+
+/// Checks packed bits by [check_raw_parts()](fn.check_raw_parts.html), and that `chunks` and `table` are the index
+/// of the bits by recomputing it in _O(N)_.
+///
+/// Shared by `Fid` and `ArchivedFid`, whose index is compared with `Chunks` and `PopcountTable`.
+pub(super) fn check_parts<C, T>(
+    byte_vec: &[u8],
+    bit_len: u64,
+    bit_order: BitOrder,
+    chunks: &C,
+    table: &T,
+) -> Result<(), FidError>
+where
+    C: PartialEq<Chunks>,
+    T: PartialEq<PopcountTable>,
+{
+    check_raw_parts(byte_vec, bit_len, bit_order)?;
+    let last_byte_len = ((bit_len - 1) % 8 + 1) as u8;
+    let rbv = RawBitVector::new(byte_vec, 0, last_byte_len).with_bit_order(bit_order);
+    if *chunks != Chunks::new(&rbv) {
+        return Err(FidError::Corrupted {
+            reason: "rank directory inconsistent with bits",
+        });
+    }
+    if *table != PopcountTable::new(Chunks::calc_block_size(bit_len)) {
+        return Err(FidError::Corrupted {
+            reason: "popcount table inconsistent with bit length",
+        });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod from_str_success_tests {
     use crate::Fid;
@@ -371,6 +566,79 @@ mod from_slice_failure_tests {
     }
 }
 
+#[cfg(test)]
+mod from_raw_parts_success_tests {
+    use crate::{BitOrder, Fid};
+    use alloc::vec::Vec;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let in_bits: Vec<bool> = $value;
+                let expected = Fid::from(&in_bits[..]);
+
+                let mut lsb_bytes = vec![0u8; in_bits.len().div_ceil(8)];
+                for (i, &bit) in in_bits.iter().enumerate() {
+                    lsb_bytes[i / 8] |= (bit as u8) << (i % 8);
+                }
+                let lsb = Fid::from_raw_parts(lsb_bytes.clone(), in_bits.len() as u64, BitOrder::LsbFirst);
+                let msb = Fid::from_raw_parts(expected.as_bytes().to_vec(), in_bits.len() as u64, BitOrder::MsbFirst);
+
+                for fid in [&lsb, &msb] {
+                    assert_eq!(fid, &expected);
+                    for i in 0..fid.len() {
+                        assert_eq!(fid[i], expected[i], "i = {}", i);
+                        assert_eq!(fid.rank(i), expected.rank(i), "i = {}", i);
+                        assert_eq!(fid.select1(i), expected.select1(i), "i = {}", i);
+                        assert_eq!(fid.select0(i), expected.select0(i), "i = {}", i);
+                    }
+                }
+                assert_eq!(lsb.as_bytes(), &lsb_bytes[..]);
+                assert_eq!(lsb.bit_order(), BitOrder::LsbFirst);
+                assert_eq!(lsb.into_raw_parts(), (lsb_bytes, in_bits.len() as u64, BitOrder::LsbFirst));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: vec![true],
+        t2: vec![false, true, false, false, true],
+        t3: (0..8).map(|i| i % 3 == 0).collect(),
+        t4: (0..9).map(|i| i % 3 == 0).collect(),
+        t5: (0..100).map(|i| i % 3 == 0 || i % 7 == 0).collect(),
+        t6: (0..1000).map(|i| i % 5 == 0 || i % 11 == 0).collect(),
+    }
+
+    #[test]
+    fn clears_unused_bits() {
+        let msb = Fid::from_raw_parts(vec![0b0100_1111], 5, BitOrder::MsbFirst);
+        assert_eq!(msb.as_bytes(), &[0b0100_1000]);
+        let lsb = Fid::from_raw_parts(vec![0b1111_0010], 5, BitOrder::LsbFirst);
+        assert_eq!(lsb.as_bytes(), &[0b0001_0010]);
+        assert_eq!(lsb, msb);
+    }
+}
+
+#[cfg(test)]
+mod from_raw_parts_failure_tests {
+    use crate::{BitOrder, Fid};
+
+    #[test]
+    #[should_panic]
+    fn empty() {
+        let _ = Fid::from_raw_parts(vec![], 0, BitOrder::LsbFirst);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_bytes() {
+        let _ = Fid::from_raw_parts(vec![0, 0], 8, BitOrder::LsbFirst);
+    }
+}
+
 #[cfg(test)]
 mod index_u64_success_tests {
     // well-tested in fid_builder::{builder_from_length_success_tests, builder_from_bit_string_success_tests}
@@ -518,35 +786,119 @@ mod rank0_0_failure_tests {
 }
 
 #[cfg(test)]
-mod select_success_tests {
-    // Tested well in tests/ (integration test)
+mod select1_success_tests {
+    use crate::Fid;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_fid_str, in_k, expected_select1) = $value;
+                assert_eq!(
+                    Fid::from(in_fid_str).select1(in_k),
+                    expected_select1
+                );
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        select1_1_1: ("0", 0, None),
+
+        select1_2_1: ("1", 0, Some(0)),
+        select1_2_2: ("1", 1, None),
+
+        select1_3_1: ("01", 0, Some(1)),
+        select1_3_2: ("01", 1, None),
+
+        select1_4_1: ("10010", 0, Some(0)),
+        select1_4_2: ("10010", 1, Some(3)),
+        select1_4_3: ("10010", 2, None),
+        select1_4_4: ("10010", 5, None),
+        select1_4_5: ("10010", u64::MAX, None),
+    }
+    // Tested more in tests/ (integration test)
 }
 
 #[cfg(test)]
-mod select_failure_tests {
+mod select0_success_tests {
     use crate::Fid;
 
-    #[test]
-    #[should_panic]
-    fn select_over_max_rank() {
-        let fid = Fid::from("00");
-        let _ = fid.select(3);
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_fid_str, in_k, expected_select0) = $value;
+                assert_eq!(
+                    Fid::from(in_fid_str).select0(in_k),
+                    expected_select0
+                );
+            }
+        )*
+        }
     }
+
+    parameterized_tests! {
+        select0_1_1: ("1", 0, None),
+
+        select0_2_1: ("0", 0, Some(0)),
+        select0_2_2: ("0", 1, None),
+
+        select0_3_1: ("10", 0, Some(1)),
+        select0_3_2: ("10", 1, None),
+
+        select0_4_1: ("10010", 0, Some(1)),
+        select0_4_2: ("10010", 1, Some(2)),
+        select0_4_3: ("10010", 2, Some(4)),
+        select0_4_4: ("10010", 3, None),
+        select0_4_5: ("10010", u64::MAX, None),
+    }
+    // Tested more in tests/ (integration test)
 }
 
 #[cfg(test)]
-mod select0_success_tests {
-    // Tested well in tests/ (integration test)
+#[allow(deprecated)]
+mod select_success_tests {
+    use crate::Fid;
+
+    #[test]
+    fn select_keeps_legacy_behaviour() {
+        let fid = Fid::from("10010");
+        assert_eq!(fid.select(0), Some(0));
+        assert_eq!(fid.select(1), Some(0));
+        assert_eq!(fid.select(2), Some(3));
+        assert_eq!(fid.select(3), None);
+    }
+
+    #[test]
+    fn select0_legacy_keeps_legacy_behaviour() {
+        let fid = Fid::from("10010");
+        assert_eq!(fid.select0_legacy(0), Some(0));
+        assert_eq!(fid.select0_legacy(1), Some(1));
+        assert_eq!(fid.select0_legacy(3), Some(4));
+        assert_eq!(fid.select0_legacy(4), None);
+    }
 }
 
 #[cfg(test)]
-mod select0_failure_tests {
+#[allow(deprecated)]
+mod select_failure_tests {
     use crate::Fid;
 
     #[test]
     #[should_panic]
     fn select_over_max_rank() {
         let fid = Fid::from("00");
-        let _ = fid.select0(3);
+        let _ = fid.select(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn select0_legacy_over_max_rank() {
+        let fid = Fid::from("00");
+        let _ = fid.select0_legacy(3);
     }
 }
@@ -0,0 +1,388 @@
+use super::{BitOrder, Fid, FidError, ReadFormat};
+use std::io::{self, Read};
+
+/// Size of the buffer `Fid::from_reader()` reads input into.
+const READ_BUF_SIZE: usize = 8 * 1024;
+
+/// Reciprocal of the ratio of bytes by which `ReadFormat::Text` input grows the buffer of packed bits.
+const TEXT_GROWTH_DIVISOR: usize = 8;
+
+/// Calls `f` with every chunk of at most `buf.len()` bytes read from `reader` until EOF.
+pub(super) fn for_each_chunk<R: Read>(
+    reader: &mut R,
+    buf: &mut [u8],
+    mut f: impl FnMut(&[u8]) -> Result<(), FidError>,
+) -> Result<(), FidError> {
+    loop {
+        match reader.read(buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) => f(&buf[..n])?,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Calls `on_bit` with every bit of `ReadFormat::Text` input from `reader`.
+pub(super) fn for_each_text_bit<R: Read>(
+    reader: &mut R,
+    buf: &mut [u8],
+    mut on_bit: impl FnMut(bool) -> Result<(), FidError>,
+) -> Result<(), FidError> {
+    let mut offset = 0u64;
+    for_each_chunk(reader, buf, |chunk| {
+        for &c in chunk {
+            match c {
+                b'0' => on_bit(false)?,
+                b'1' => on_bit(true)?,
+                b'_' => (),
+                c if c.is_ascii_whitespace() => (),
+                _ => return Err(FidError::InvalidCharacter { byte: c, offset }),
+            }
+            offset += 1;
+        }
+        Ok(())
+    })
+}
+
+/// Parser of `ReadFormat::Positions`, which calls `on_position` with every position read.
+pub(super) struct PositionsParser<F> {
+    on_position: F,
+
+    /// Line number (1-origin) of the current line.
+    line: u64,
+
+    /// Position being read in the current line.
+    position: Option<u64>,
+
+    /// Whether a whitespace follows `position` in the current line.
+    position_ended: bool,
+}
+
+impl<F: FnMut(u64) -> Result<(), FidError>> PositionsParser<F> {
+    pub(super) fn new(on_position: F) -> Self {
+        PositionsParser {
+            on_position,
+            line: 1,
+            position: None,
+            position_ended: false,
+        }
+    }
+
+    pub(super) fn feed(&mut self, c: u8) -> Result<(), FidError> {
+        let invalid = FidError::InvalidPosition { line: self.line };
+        match c {
+            b'0'..=b'9' => {
+                if self.position_ended {
+                    return Err(invalid);
+                }
+                let position = self
+                    .position
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|p| p.checked_add((c - b'0') as u64))
+                    .ok_or(invalid)?;
+                self.position = Some(position);
+            }
+            b'\n' => {
+                self.end_line()?;
+                self.line += 1;
+            }
+            c if c.is_ascii_whitespace() => self.position_ended = self.position.is_some(),
+            _ => return Err(invalid),
+        }
+        Ok(())
+    }
+
+    /// Must be called at the end of input.
+    pub(super) fn end_line(&mut self) -> Result<(), FidError> {
+        self.position_ended = false;
+        match self.position.take() {
+            Some(position) => (self.on_position)(position),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Fid {
+    /// Constructor from `reader` in `format`.
+    ///
+    /// Input is read in buffers of bounded size, so that the peak memory usage is about the size of the built `Fid`.
+    /// `reader` does not have to be buffered.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::{BitOrder, Fid, ReadFormat};
+    ///
+    /// let fid = Fid::from_reader("0100\n1\n".as_bytes(), ReadFormat::Text).unwrap();
+    /// assert_eq!(fid, Fid::from("0100_1"));
+    ///
+    /// let format = ReadFormat::Bytes { bit_len: 5, bit_order: BitOrder::MsbFirst };
+    /// let fid = Fid::from_reader(&[0b0100_1000u8][..], format).unwrap();
+    /// assert_eq!(fid, Fid::from("0100_1"));
+    ///
+    /// let fid = Fid::from_reader("4\n1\n".as_bytes(), ReadFormat::Positions { len: 5 }).unwrap();
+    /// assert_eq!(fid, Fid::from("0100_1"));
+    /// ```
+    ///
+    /// # Errors
+    /// - `FidError::ZeroLength` when the bit length is _0_.
+    /// - `FidError::InvalidCharacter` when `ReadFormat::Text` input has an unexpected character.
+    /// - `FidError::TruncatedInput` when `ReadFormat::Bytes` input is shorter than _ceil(`bit_len` / 8)_ bytes.
+    /// - `FidError::InvalidPosition` when a line of `ReadFormat::Positions` input is not a `u64` number.
+    /// - `FidError::PositionOutOfRange` when a position of `ReadFormat::Positions` input is _>= `len`_.
+    /// - `FidError::Io` when `reader` fails.
+    pub fn from_reader<R: Read>(mut reader: R, format: ReadFormat) -> Result<Self, FidError> {
+        match format {
+            ReadFormat::Text => Self::read_text(&mut reader),
+            ReadFormat::Bytes { bit_len, bit_order } => {
+                Self::read_bytes(&mut reader, bit_len, bit_order)
+            }
+            ReadFormat::Positions { len } => Self::read_positions(&mut reader, len),
+        }
+    }
+
+    /// Bits are packed directly into the buffer of the built `Fid`, without another copy of the bits.
+    /// The buffer grows by _1 / `TEXT_GROWTH_DIVISOR`_ of its length (instead of doubling as `Vec` does),
+    /// so that its unused capacity is at most about that ratio of the bits.
+    fn read_text<R: Read>(reader: &mut R) -> Result<Self, FidError> {
+        let mut byte_vec: Vec<u8> = Vec::new();
+        let push = |byte_vec: &mut Vec<u8>, byte: u8| {
+            if byte_vec.len() == byte_vec.capacity() {
+                byte_vec
+                    .reserve_exact((byte_vec.len() / TEXT_GROWTH_DIVISOR).max(TEXT_GROWTH_DIVISOR));
+            }
+            byte_vec.push(byte);
+        };
+        let mut byte = 0u8;
+        let mut bit_len = 0u64;
+        for_each_text_bit(reader, &mut [0; READ_BUF_SIZE], |bit| {
+            if bit {
+                byte |= 0x80 >> (bit_len % 8);
+            }
+            bit_len += 1;
+            if bit_len.is_multiple_of(8) {
+                push(&mut byte_vec, byte);
+                byte = 0;
+            }
+            Ok(())
+        })?;
+
+        if bit_len == 0 {
+            return Err(FidError::ZeroLength);
+        }
+        if !bit_len.is_multiple_of(8) {
+            push(&mut byte_vec, byte);
+        }
+        Ok(Fid::from_raw_parts(byte_vec, bit_len, BitOrder::MsbFirst))
+    }
+
+    fn read_bytes<R: Read>(
+        reader: &mut R,
+        bit_len: u64,
+        bit_order: BitOrder,
+    ) -> Result<Self, FidError> {
+        if bit_len == 0 {
+            return Err(FidError::ZeroLength);
+        }
+
+        // Read directly into the final buffer.
+        let mut byte_vec: Vec<u8> = vec![0; bit_len.div_ceil(8) as usize];
+        let mut filled = 0;
+        while filled < byte_vec.len() {
+            match reader.read(&mut byte_vec[filled..]) {
+                Ok(0) => {
+                    return Err(FidError::TruncatedInput {
+                        expected: byte_vec.len() as u64,
+                        actual: filled as u64,
+                    })
+                }
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(Fid::from_raw_parts(byte_vec, bit_len, bit_order))
+    }
+
+    fn read_positions<R: Read>(reader: &mut R, len: u64) -> Result<Self, FidError> {
+        if len == 0 {
+            return Err(FidError::ZeroLength);
+        }
+
+        let mut byte_vec: Vec<u8> = vec![0; len.div_ceil(8) as usize];
+        let mut parser = PositionsParser::new(|position| {
+            if position >= len {
+                return Err(FidError::PositionOutOfRange { position, len });
+            }
+            byte_vec[(position / 8) as usize] |= 0x80 >> (position % 8);
+            Ok(())
+        });
+        for_each_chunk(reader, &mut [0; READ_BUF_SIZE], |chunk| {
+            chunk.iter().try_for_each(|&c| parser.feed(c))
+        })?;
+        parser.end_line()?;
+        Ok(Fid::from_raw_parts(byte_vec, len, BitOrder::MsbFirst))
+    }
+}
+
+#[cfg(test)]
+mod from_reader_success_tests {
+    use crate::{BitOrder, Fid, ReadFormat};
+    use std::io::{self, Read};
+
+    /// Reader returning at most 1 byte per `read()` to test boundaries of buffers.
+    struct OneByteReader<'a>(&'a [u8]);
+
+    impl Read for OneByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_input, in_format, expected_fid_str): (&[u8], ReadFormat, &str) = $value;
+                let expected = Fid::from(expected_fid_str);
+                assert_eq!(Fid::from_reader(in_input, in_format).unwrap(), expected);
+                assert_eq!(Fid::from_reader(OneByteReader(in_input), in_format).unwrap(), expected);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        text_1: (b"0", ReadFormat::Text, "0"),
+        text_2: (b"0100_1", ReadFormat::Text, "0100_1"),
+        text_3: (b" 0100\r\n1\t\n", ReadFormat::Text, "0100_1"),
+        text_4: (b"01001000_01001000", ReadFormat::Text, "01001000_01001000"),
+        bytes_1: (
+            &[0b0100_1000],
+            ReadFormat::Bytes { bit_len: 5, bit_order: BitOrder::MsbFirst },
+            "0100_1"
+        ),
+        bytes_2: (
+            &[0b0001_0010],
+            ReadFormat::Bytes { bit_len: 5, bit_order: BitOrder::LsbFirst },
+            "0100_1"
+        ),
+        bytes_3: (
+            &[0xff, 0b1000_0000, 0xff],
+            ReadFormat::Bytes { bit_len: 9, bit_order: BitOrder::MsbFirst },
+            "11111111_1"
+        ),
+        positions_1: (b"", ReadFormat::Positions { len: 3 }, "000"),
+        positions_2: (b"1\n4", ReadFormat::Positions { len: 5 }, "0100_1"),
+        positions_3: (b"\n 4 \r\n\n1\n1\n", ReadFormat::Positions { len: 5 }, "0100_1"),
+        positions_4: (b"8\n0\n", ReadFormat::Positions { len: 9 }, "10000000_1"),
+    }
+
+    #[test]
+    fn bytes_not_read_after_bit_len() {
+        let mut input: &[u8] = &[0b0100_1000, 0xab];
+        let format = ReadFormat::Bytes {
+            bit_len: 5,
+            bit_order: BitOrder::MsbFirst,
+        };
+        assert_eq!(
+            Fid::from_reader(&mut input, format).unwrap(),
+            Fid::from("0100_1")
+        );
+        assert_eq!(input, [0xab]);
+    }
+
+    #[test]
+    fn text_larger_than_buffer() {
+        let text: String = (0..100_000)
+            .map(|i| if i % 3 == 0 { '1' } else { '0' })
+            .collect();
+        let fid = Fid::from_reader(text.as_bytes(), ReadFormat::Text).unwrap();
+        assert_eq!(fid, Fid::from(text.as_str()));
+    }
+
+    #[test]
+    fn text_capacity_bounded() {
+        let len = 1_000_003;
+        let text: String = (0..len)
+            .map(|i| if i % 5 == 0 { '1' } else { '0' })
+            .collect();
+        let fid = Fid::from_reader(text.as_bytes(), ReadFormat::Text).unwrap();
+        assert_eq!(fid, Fid::from(text.as_str()));
+        let bytes = fid.byte_vec.len();
+        assert!(fid.byte_vec.capacity() <= bytes + bytes / super::TEXT_GROWTH_DIVISOR + 1);
+    }
+}
+
+#[cfg(test)]
+mod from_reader_failure_tests {
+    use crate::{BitOrder, Fid, FidError, ReadFormat};
+    use std::io::{self, Read};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_input, in_format, expected_err): (&[u8], ReadFormat, FidError) = $value;
+                assert_eq!(Fid::from_reader(in_input, in_format), Err(expected_err));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        text_empty: (b"_ \n", ReadFormat::Text, FidError::ZeroLength),
+        text_invalid: (b"01\n2", ReadFormat::Text, FidError::InvalidCharacter { byte: b'2', offset: 3 }),
+        bytes_zero_length: (
+            &[0],
+            ReadFormat::Bytes { bit_len: 0, bit_order: BitOrder::MsbFirst },
+            FidError::ZeroLength
+        ),
+        bytes_truncated: (
+            &[0, 0],
+            ReadFormat::Bytes { bit_len: 17, bit_order: BitOrder::MsbFirst },
+            FidError::TruncatedInput { expected: 3, actual: 2 }
+        ),
+        positions_zero_length: (b"", ReadFormat::Positions { len: 0 }, FidError::ZeroLength),
+        positions_out_of_range: (
+            b"1\n5\n",
+            ReadFormat::Positions { len: 5 },
+            FidError::PositionOutOfRange { position: 5, len: 5 }
+        ),
+        positions_not_number: (b"1\n\nx\n", ReadFormat::Positions { len: 5 }, FidError::InvalidPosition { line: 3 }),
+        positions_negative: (b"-1", ReadFormat::Positions { len: 5 }, FidError::InvalidPosition { line: 1 }),
+        positions_two_in_line: (b"1 2", ReadFormat::Positions { len: 5 }, FidError::InvalidPosition { line: 1 }),
+        positions_overflow: (
+            b"18446744073709551616",
+            ReadFormat::Positions { len: 5 },
+            FidError::InvalidPosition { line: 1 }
+        ),
+    }
+
+    #[test]
+    fn io_error() {
+        struct BrokenReader;
+
+        impl Read for BrokenReader {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken"))
+            }
+        }
+
+        let err = Fid::from_reader(BrokenReader, ReadFormat::Text).unwrap_err();
+        assert_eq!(
+            err,
+            FidError::Io {
+                kind: io::ErrorKind::Other,
+                message: "broken".to_string(),
+            }
+        );
+        assert_eq!(err.to_string(), "I/O error: broken");
+    }
+}
@@ -0,0 +1,191 @@
+use super::chunks::ChunksBuilder;
+use super::{BitOrder, Chunks, Fid, FidError};
+use crate::internal_data_structure::popcount_table::PopcountTable;
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl Fid {
+    /// Constructor from positions of _1_ in ascending order, without materializing `len` booleans.
+    ///
+    /// The same position may appear more than once.
+    ///
+    /// The index is built while writing the bits, without another pass over them.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from_positions(vec![1, 4], 5).unwrap();
+    /// assert_eq!(fid, Fid::from("0100_1"));
+    /// ```
+    ///
+    /// # Errors
+    /// - `FidError::ZeroLength` when _`len` == 0_.
+    /// - `FidError::PositionOutOfRange` when a position is _>= `len`_.
+    /// - `FidError::UnsortedPositions` when a position is less than the previous one.
+    pub fn from_positions<I: IntoIterator<Item = u64>>(
+        positions: I,
+        len: u64,
+    ) -> Result<Self, FidError> {
+        if len == 0 {
+            return Err(FidError::ZeroLength);
+        }
+
+        let mut byte_vec: Vec<u8> = vec![0; len.div_ceil(8) as usize];
+        let mut chunks = ChunksBuilder::new(len);
+        let mut previous: Option<u64> = None;
+        for position in positions {
+            if position >= len {
+                return Err(FidError::PositionOutOfRange { position, len });
+            }
+            match previous {
+                Some(previous) if previous > position => {
+                    return Err(FidError::UnsortedPositions { previous, position });
+                }
+                Some(previous) if previous == position => continue,
+                _ => (),
+            }
+            byte_vec[(position / 8) as usize] |= 0x80 >> (position % 8);
+            chunks.count_one(position);
+            previous = Some(position);
+        }
+
+        Ok(Fid {
+            byte_vec,
+            bit_len: len,
+            bit_order: BitOrder::MsbFirst,
+            chunks: chunks.finish(),
+            table: PopcountTable::new(Chunks::calc_block_size(len)),
+        })
+    }
+
+    /// Constructor from positions of _1_ in any order. Positions are sorted and deduplicated first.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from_unsorted_positions(vec![4, 1, 4], 5).unwrap();
+    /// assert_eq!(fid, Fid::from("0100_1"));
+    /// ```
+    ///
+    /// # Errors
+    /// - `FidError::ZeroLength` when _`len` == 0_.
+    /// - `FidError::PositionOutOfRange` when a position is _>= `len`_.
+    pub fn from_unsorted_positions<I: IntoIterator<Item = u64>>(
+        positions: I,
+        len: u64,
+    ) -> Result<Self, FidError> {
+        let mut positions: Vec<u64> = positions.into_iter().collect();
+        positions.sort_unstable();
+        positions.dedup();
+        Fid::from_positions(positions, len)
+    }
+}
+
+#[cfg(test)]
+mod from_positions_success_tests {
+    use crate::{BitOrder, Fid};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_positions, in_len, expected_fid_str): (&[u64], u64, &str) = $value;
+                let expected = Fid::from(expected_fid_str);
+
+                let fid = Fid::from_positions(in_positions.iter().copied(), in_len).unwrap();
+                assert_eq!(fid, expected);
+                assert_eq!(fid.validate(), Ok(()));
+
+                let mut reversed = in_positions.to_vec();
+                reversed.reverse();
+                let fid = Fid::from_unsorted_positions(reversed, in_len).unwrap();
+                assert_eq!(fid, expected);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[], 1, "0"),
+        t2: (&[0], 1, "1"),
+        t3: (&[1, 4], 5, "0100_1"),
+        t4: (&[1, 1, 4, 4], 5, "0100_1"),
+        t5: (&[], 9, "00000000_0"),
+        t6: (&[7, 8], 9, "00000001_1"),
+        t7: (&[0, 1, 2, 3, 4, 5, 6, 7, 8], 9, "11111111_1"),
+    }
+
+    #[test]
+    fn same_as_from_slice() {
+        let bits: Vec<bool> = (0..1000).map(|i| i % 3 == 0 || i % 7 == 0).collect();
+        let positions = (0..1000u64).filter(|&i| bits[i as usize]);
+        let fid = Fid::from_positions(positions, 1000).unwrap();
+        assert_eq!(fid, Fid::from(&bits[..]));
+        assert_eq!(fid.validate(), Ok(()));
+        for i in 0..1000 {
+            assert_eq!(fid.rank(i), Fid::from(&bits[..]).rank(i), "i = {}", i);
+        }
+    }
+
+    #[test]
+    fn index_same_as_from_raw_parts() {
+        let len: u64 = (1 << 20) + 5;
+        let mut byte_vec = vec![0u8; len.div_ceil(8) as usize];
+        for i in (0..len).step_by(3) {
+            byte_vec[(i / 8) as usize] |= 0x80 >> (i % 8);
+        }
+        let expected = Fid::from_raw_parts(byte_vec, len, BitOrder::MsbFirst);
+
+        let fid = Fid::from_positions((0..len).step_by(3), len).unwrap();
+        assert_eq!(fid, expected);
+        // `==` compares only the bits.
+        assert_eq!(fid.chunks, expected.chunks);
+        assert_eq!(fid.table, expected.table);
+    }
+}
+
+#[cfg(test)]
+mod from_positions_failure_tests {
+    use crate::{Fid, FidError};
+    use alloc::vec;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_positions, in_len, expected_err) = $value;
+                assert_eq!(Fid::from_positions(in_positions, in_len), Err(expected_err));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (vec![], 0, FidError::ZeroLength),
+        t2: (vec![5], 5, FidError::PositionOutOfRange { position: 5, len: 5 }),
+        t3: (vec![1, 100], 5, FidError::PositionOutOfRange { position: 100, len: 5 }),
+        t4: (vec![4, 1], 5, FidError::UnsortedPositions { previous: 4, position: 1 }),
+        t5: (vec![0, 3, 2, 4], 5, FidError::UnsortedPositions { previous: 3, position: 2 }),
+    }
+
+    #[test]
+    fn unsorted_out_of_range() {
+        assert_eq!(
+            Fid::from_unsorted_positions(vec![7, 1, 5], 5),
+            Err(FidError::PositionOutOfRange {
+                position: 5,
+                len: 5
+            })
+        );
+        assert_eq!(
+            Fid::from_unsorted_positions(vec![], 0),
+            Err(FidError::ZeroLength)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use super::fid_impl::{check_parts, rank_by, select_by};
+use super::{ArchivedBitOrder, ArchivedFid, BitOrder, FidError};
+use crate::internal_data_structure::raw_bit_vector::RawBitVector;
+use core::ops::Index;
+
+/// Queries directly on the archived bytes, without deserialization.
+///
+/// `rkyv::access()` checks the layout of the bytes but not the consistency of the bits and the index.
+/// Queries on an archive from an untrusted source may return wrong values or panic
+/// unless [validate()](#method.validate) returns `Ok(())`.
+///
+/// # Examples
+/// ```
+/// use fid_rs::{ArchivedFid, Fid};
+///
+/// let fid = Fid::from("0100_1");
+/// let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&fid).unwrap();
+///
+/// let archived = rkyv::access::<ArchivedFid, rkyv::rancor::Error>(&bytes).unwrap();
+/// assert_eq!(archived.rank(4), 2);
+/// assert_eq!(archived.select1(1), Some(4));
+/// assert!(archived[1]);
+/// ```
+impl ArchivedFid {
+    /// Returns bit length.
+    pub fn len(&self) -> u64 {
+        self.bit_len.to_native()
+    }
+
+    /// Returns whether empty. Always `false` for a valid archive.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the order of bits in the archived bytes.
+    pub fn bit_order(&self) -> BitOrder {
+        match self.bit_order {
+            ArchivedBitOrder::MsbFirst => BitOrder::MsbFirst,
+            ArchivedBitOrder::LsbFirst => BitOrder::LsbFirst,
+        }
+    }
+
+    /// Returns `i`-th bit.
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn get(&self, i: u64) -> bool {
+        self.rbv().access(i)
+    }
+
+    /// Same as [Fid::rank()](struct.Fid.html#method.rank).
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn rank(&self, i: u64) -> u64 {
+        rank_by(
+            &self.rbv(),
+            i,
+            |i_chunk| self.chunks.chunks[i_chunk as usize].to_native(),
+            |i_block| self.chunks.blocks[i_block as usize].to_native(),
+            |block_bits| self.table.popcount(block_bits),
+        )
+    }
+
+    /// Same as [Fid::rank0()](struct.Fid.html#method.rank0).
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn rank0(&self, i: u64) -> u64 {
+        (i + 1) - self.rank(i)
+    }
+
+    /// Same as [Fid::select1()](struct.Fid.html#method.select1).
+    pub fn select1(&self, k: u64) -> Option<u64> {
+        select_by(self.len(), k, |i| self.rank(i))
+    }
+
+    /// Same as [Fid::select0()](struct.Fid.html#method.select0).
+    pub fn select0(&self, k: u64) -> Option<u64> {
+        select_by(self.len(), k, |i| self.rank0(i))
+    }
+
+    /// Same as [Fid::select()](struct.Fid.html#method.select).
+    ///
+    /// # Panics
+    /// When _`num` > `self.len()`_.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `select1(k)`, which takes 0-origin `k` and returns `None` instead of panicking"
+    )]
+    pub fn select(&self, num: u64) -> Option<u64> {
+        assert!(num <= self.len());
+        match num {
+            0 => Some(0),
+            _ => self.select1(num - 1),
+        }
+    }
+
+    /// Same as [Fid::select0_legacy()](struct.Fid.html#method.select0_legacy).
+    ///
+    /// # Panics
+    /// When _`num` > `self.len()`_.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use `select0(k)`, which takes 0-origin `k` and returns `None` instead of panicking"
+    )]
+    pub fn select0_legacy(&self, num: u64) -> Option<u64> {
+        assert!(num <= self.len());
+        match num {
+            0 => Some(0),
+            _ => self.select0(num - 1),
+        }
+    }
+
+    /// Same as [Fid::validate()](struct.Fid.html#method.validate): checks the integrity of the archived bits and index,
+    /// by recomputing the index in _O(N)_.
+    ///
+    /// # Errors
+    /// - `FidError::ZeroLength` when the bit length is _0_.
+    /// - `FidError::Corrupted` when the number of bytes is inconsistent with the bit length,
+    ///   or the index is inconsistent with the bits.
+    /// - `FidError::NonZeroPaddingBits` when an unused bit in the last byte is _1_.
+    pub fn validate(&self) -> Result<(), FidError> {
+        check_parts(
+            &self.byte_vec,
+            self.len(),
+            self.bit_order(),
+            &self.chunks,
+            &self.table,
+        )
+    }
+
+    fn rbv(&self) -> RawBitVector<'_> {
+        let last_byte_len = ((self.len() - 1) % 8 + 1) as u8;
+        RawBitVector::new(&self.byte_vec, 0, last_byte_len).with_bit_order(self.bit_order())
+    }
+}
+
+static TRUE: bool = true;
+static FALSE: bool = false;
+
+impl Index<u64> for ArchivedFid {
+    type Output = bool;
+
+    /// Returns `i`-th element.
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    fn index(&self, index: u64) -> &Self::Output {
+        if self.get(index) {
+            &TRUE
+        } else {
+            &FALSE
+        }
+    }
+}
+
+#[cfg(test)]
+mod archived_fid_success_tests {
+    use crate::{ArchivedFid, BitOrder, Fid};
+    use rkyv::rancor::Error;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let fid: Fid = $value;
+                let bytes = rkyv::to_bytes::<Error>(&fid).unwrap();
+                let archived = rkyv::access::<ArchivedFid, Error>(&bytes).unwrap();
+
+                assert_eq!(archived.len(), fid.len());
+                assert_eq!(archived.bit_order(), fid.bit_order());
+                assert_eq!(archived.validate(), Ok(()));
+                for i in 0..fid.len() {
+                    assert_eq!(archived[i], fid[i], "i = {}", i);
+                    assert_eq!(archived.rank(i), fid.rank(i), "i = {}", i);
+                    assert_eq!(archived.rank0(i), fid.rank0(i), "i = {}", i);
+                }
+                for k in 0..=fid.len() {
+                    assert_eq!(archived.select1(k), fid.select1(k), "k = {}", k);
+                    assert_eq!(archived.select0(k), fid.select0(k), "k = {}", k);
+                }
+                #[allow(deprecated)]
+                for num in 0..=fid.len() {
+                    assert_eq!(archived.select(num), fid.select(num), "num = {}", num);
+                    assert_eq!(archived.select0_legacy(num), fid.select0_legacy(num), "num = {}", num);
+                }
+
+                let deserialized = rkyv::deserialize::<Fid, Error>(archived).unwrap();
+                assert_eq!(deserialized, fid);
+                assert_eq!(deserialized.validate(), Ok(()));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: Fid::from("0"),
+        t2: Fid::from("1"),
+        t3: Fid::from("0100_1"),
+        t4: Fid::from_raw_parts(vec![0b0001_0010, 0xff], 13, BitOrder::LsbFirst),
+        t5: Fid::from(&[true; 1000][..]),
+        t6: Fid::from_runs(true, vec![100, 1000, 10, 3003]).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod archived_fid_failure_tests {
+    use crate::internal_data_structure::popcount_table::PopcountTable;
+    use crate::{ArchivedFid, Fid, FidError};
+    use rkyv::rancor::Error;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_corrupt, expected_error): (fn(&mut Fid), FidError) = $value;
+                let mut fid = Fid::from_runs(true, vec![100, 1000, 10, 3003]).unwrap();
+                in_corrupt(&mut fid);
+                let bytes = rkyv::to_bytes::<Error>(&fid).unwrap();
+                let archived = rkyv::access::<ArchivedFid, Error>(&bytes).unwrap();
+                assert_eq!(archived.validate(), Err(expected_error));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (|fid| fid.bit_len = 0, FidError::ZeroLength),
+        t2: (|fid| { fid.byte_vec.pop(); }, FidError::Corrupted { reason: "byte length inconsistent with bit length" }),
+        t3: (|fid| *fid.byte_vec.last_mut().unwrap() |= 1, FidError::NonZeroPaddingBits { bit_len: 4113 }),
+        t4: (|fid| fid.byte_vec[0] ^= 0x10, FidError::Corrupted { reason: "rank directory inconsistent with bits" }),
+        t5: (|fid| fid.chunks.chunks[3] += 1, FidError::Corrupted { reason: "rank directory inconsistent with bits" }),
+        t6: (|fid| { fid.chunks.blocks.pop(); }, FidError::Corrupted { reason: "rank directory inconsistent with bits" }),
+        t7: (|fid| fid.table = PopcountTable::new(3), FidError::Corrupted { reason: "popcount table inconsistent with bit length" }),
+    }
+}
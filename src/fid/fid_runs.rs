@@ -0,0 +1,262 @@
+use super::{BitOrder, Fid, FidError, Runs};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Sets _[`start`, `end`)_ of MSB-first packed bits to _1_, a whole byte at a time except both ends.
+fn fill_ones(byte_vec: &mut [u8], start: u64, end: u64) {
+    if start >= end {
+        return;
+    }
+    let (first_byte, last_byte) = ((start / 8) as usize, ((end - 1) / 8) as usize);
+    let head_mask = 0xffu8 >> (start % 8);
+    let tail_mask = 0xffu8 << (7 - (end - 1) % 8);
+    if first_byte == last_byte {
+        byte_vec[first_byte] |= head_mask & tail_mask;
+    } else {
+        byte_vec[first_byte] |= head_mask;
+        byte_vec[first_byte + 1..last_byte].fill(0xff);
+        byte_vec[last_byte] |= tail_mask;
+    }
+}
+
+impl Fid {
+    /// Constructor from ranges of _1_. Other bits are _0_.
+    ///
+    /// Ranges may come in any order and may overlap. Empty ranges are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from_ranges(vec![1..2, 4..7], 9).unwrap();
+    /// assert_eq!(fid, Fid::from("0100_111_00"));
+    /// ```
+    ///
+    /// # Errors
+    /// - `FidError::ZeroLength` when _`len` == 0_.
+    /// - `FidError::InvalidRange` when a range has _start > end_ or _end > `len`_.
+    pub fn from_ranges<I: IntoIterator<Item = Range<u64>>>(
+        ranges: I,
+        len: u64,
+    ) -> Result<Self, FidError> {
+        if len == 0 {
+            return Err(FidError::ZeroLength);
+        }
+
+        let mut byte_vec: Vec<u8> = vec![0; len.div_ceil(8) as usize];
+        for Range { start, end } in ranges {
+            if start > end || end > len {
+                return Err(FidError::InvalidRange { start, end, len });
+            }
+            fill_ones(&mut byte_vec, start, end);
+        }
+
+        Ok(Fid::from_raw_parts(byte_vec, len, BitOrder::MsbFirst))
+    }
+
+    /// Constructor from run lengths: `first_bit` repeats `runs[0]` times, then `!first_bit` repeats `runs[1]` times, and so on.
+    ///
+    /// Inverse of [runs()](#method.runs) when `runs` is not empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from_runs(false, vec![1, 1, 2, 3, 2]).unwrap();
+    /// assert_eq!(fid, Fid::from("0100_111_00"));
+    /// ```
+    ///
+    /// # Errors
+    /// - `FidError::ZeroLength` when the sum of `runs` is _0_.
+    /// - `FidError::LengthOverflow` when the sum of `runs` exceeds `u64::MAX`.
+    pub fn from_runs<I: IntoIterator<Item = u64>>(
+        first_bit: bool,
+        runs: I,
+    ) -> Result<Self, FidError> {
+        let mut byte_vec: Vec<u8> = Vec::new();
+        let mut len: u64 = 0;
+        let mut bit = first_bit;
+        for run in runs {
+            let end = len.checked_add(run).ok_or(FidError::LengthOverflow)?;
+            byte_vec.resize(end.div_ceil(8) as usize, 0);
+            if bit {
+                fill_ones(&mut byte_vec, len, end);
+            }
+            len = end;
+            bit = !bit;
+        }
+
+        if len == 0 {
+            return Err(FidError::ZeroLength);
+        }
+        Ok(Fid::from_raw_parts(byte_vec, len, BitOrder::MsbFirst))
+    }
+
+    /// Creates an iterator over maximal runs of the same bit, as _(bit, start, length)_.
+    ///
+    /// Runs are found 64 bits at a time.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from("0100_111_00");
+    /// let runs: Vec<(bool, u64, u64)> = fid.runs().collect();
+    /// assert_eq!(
+    ///     runs,
+    ///     vec![(false, 0, 1), (true, 1, 1), (false, 2, 2), (true, 4, 3), (false, 7, 2)]
+    /// );
+    /// ```
+    pub fn runs(&self) -> Runs<'_> {
+        Runs { fid: self, pos: 0 }
+    }
+}
+
+impl Iterator for Runs<'_> {
+    type Item = (bool, u64, u64);
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.fid.len();
+        if self.pos >= len {
+            return None;
+        }
+
+        let start = self.pos;
+        let bit = self.fid[start];
+        loop {
+            let offset = self.pos % 64;
+            let word = self.fid.msb_word(self.pos / 64, len) << offset;
+            let same = if bit {
+                word.leading_ones()
+            } else {
+                word.leading_zeros()
+            };
+            let same = (same as u64).min(64 - offset);
+            self.pos = (self.pos + same).min(len);
+            if self.pos >= len || same < 64 - offset {
+                break;
+            }
+        }
+        Some((bit, start, self.pos - start))
+    }
+}
+
+#[cfg(test)]
+mod runs_success_tests {
+    use crate::Fid;
+    use alloc::vec::Vec;
+    use core::ops::Range;
+
+    fn sample_bits(len: usize) -> Vec<bool> {
+        (0..len).map(|i| (i / 5) % 3 == 0 || i % 70 > 60).collect()
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let in_len: usize = $value;
+                let bits = sample_bits(in_len);
+                let fid = Fid::from(&bits[..]);
+
+                let runs: Vec<(bool, u64, u64)> = fid.runs().collect();
+                let mut pos = 0;
+                for (i, &(bit, start, run_len)) in runs.iter().enumerate() {
+                    assert_eq!(start, pos);
+                    assert!(run_len > 0);
+                    assert!(bits[start as usize..(start + run_len) as usize].iter().all(|&b| b == bit));
+                    if i > 0 {
+                        assert_ne!(runs[i - 1].0, bit);
+                    }
+                    pos += run_len;
+                }
+                assert_eq!(pos, in_len as u64);
+
+                let first_bit = runs[0].0;
+                let from_runs = Fid::from_runs(first_bit, runs.iter().map(|r| r.2)).unwrap();
+                assert_eq!(from_runs, fid);
+
+                let ranges = runs.iter().filter(|r| r.0).map(|r| r.1..r.1 + r.2);
+                let from_ranges = Fid::from_ranges(ranges, in_len as u64).unwrap();
+                assert_eq!(from_ranges, fid);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: 1,
+        t2: 5,
+        t3: 8,
+        t4: 63,
+        t5: 64,
+        t6: 65,
+        t7: 200,
+        t8: 1000,
+    }
+
+    #[test]
+    fn ranges_in_any_order_and_overlapping() {
+        let ranges: Vec<Range<u64>> = vec![20..30, 3..3, 0..2, 25..40, 39..40];
+        let fid = Fid::from_ranges(ranges, 42).unwrap();
+        let expected: Vec<bool> = (0..42).map(|i| i < 2 || (20..40).contains(&i)).collect();
+        assert_eq!(fid, Fid::from(&expected[..]));
+    }
+
+    #[test]
+    fn runs_with_zero_lengths() {
+        let fid = Fid::from_runs(true, vec![0, 2, 0, 3, 1]).unwrap();
+        assert_eq!(fid, Fid::from("00_000_1"));
+    }
+
+    #[test]
+    fn long_runs() {
+        let fid = Fid::from_runs(true, vec![130, 1, 200]).unwrap();
+        let runs: Vec<(bool, u64, u64)> = fid.runs().collect();
+        assert_eq!(runs, [(true, 0, 130), (false, 130, 1), (true, 131, 200)]);
+    }
+}
+
+#[cfg(test)]
+mod runs_failure_tests {
+    use crate::{Fid, FidError};
+    use alloc::vec;
+    use core::ops::Range;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_ranges, in_len, expected_err) = $value;
+                assert_eq!(Fid::from_ranges(in_ranges, in_len), Err(expected_err));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (vec![], 0, FidError::ZeroLength),
+        t2: (vec![1..2, 0..6], 5, FidError::InvalidRange { start: 0, end: 6, len: 5 }),
+        t3: (vec![0..1, Range { start: 3, end: 2 }], 5, FidError::InvalidRange { start: 3, end: 2, len: 5 }),
+    }
+
+    #[test]
+    fn runs_sum_to_zero() {
+        assert_eq!(Fid::from_runs(true, vec![]), Err(FidError::ZeroLength));
+        assert_eq!(Fid::from_runs(false, vec![0, 0]), Err(FidError::ZeroLength));
+    }
+
+    #[test]
+    fn runs_sum_overflows() {
+        assert_eq!(
+            Fid::from_runs(true, vec![1, u64::MAX]),
+            Err(FidError::LengthOverflow)
+        );
+        assert_eq!(
+            Fid::from_runs(false, vec![3, 0, u64::MAX - 2]),
+            Err(FidError::LengthOverflow)
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use super::{BitOrder, Fid, FidError};
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// What `Fid` serializes into in non-human-readable formats. The index is not included.
+#[derive(Serialize)]
+#[serde(rename = "Fid")]
+struct CompactFidRef<'a> {
+    bit_len: u64,
+    bit_order: BitOrder,
+    bytes: Bytes<'a>,
+}
+
+/// What `Fid` deserializes from in non-human-readable formats.
+#[derive(Deserialize)]
+#[serde(rename = "Fid")]
+struct CompactFid {
+    bit_len: u64,
+    bit_order: BitOrder,
+    bytes: ByteBuf,
+}
+
+/// Serialized by `serialize_bytes()`, which many binary formats write in a row.
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Deserialized from bytes, or from a sequence of `u8` for formats without bytes.
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ByteBufVisitor;
+
+        impl<'de> Visitor<'de> for ByteBufVisitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("packed bits")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+                // Size hint is untrusted input.
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1 << 12));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(ByteBuf(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+/// Accepts both `Fid::to_hex()` and `Display` of `Fid`.
+struct TextVisitor;
+
+impl Visitor<'_> for TextVisitor {
+    type Value = Fid;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("\"<bit length>:<hex>\" or a string of '0' and '1'")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Fid, E> {
+        if v.contains(':') {
+            Fid::from_hex(v)
+        } else {
+            parse_bits(v)
+        }
+        .map_err(E::custom)
+    }
+}
+
+/// Same as `Fid::from::<&str>()` but returns an error instead of panicking.
+fn parse_bits(s: &str) -> Result<Fid, FidError> {
+    let mut byte_vec: Vec<u8> = Vec::with_capacity(s.len().div_ceil(8));
+    let mut bit_len = 0u64;
+    for (offset, &byte) in s.as_bytes().iter().enumerate() {
+        let bit = match byte {
+            b'0' => false,
+            b'1' => true,
+            b'_' => continue,
+            _ => {
+                return Err(FidError::InvalidCharacter {
+                    byte,
+                    offset: offset as u64,
+                })
+            }
+        };
+        if bit_len.is_multiple_of(8) {
+            byte_vec.push(0);
+        }
+        if bit {
+            byte_vec[(bit_len / 8) as usize] |= 0x80 >> (bit_len % 8);
+        }
+        bit_len += 1;
+    }
+    Fid::try_from_raw_parts(byte_vec, bit_len, BitOrder::MsbFirst)
+}
+
+/// Only the bit length, the bit order and the packed bits are serialized. The index is rebuilt on deserialization.
+///
+/// Human-readable formats (e.g. JSON and YAML) get [to_hex()](#method.to_hex) as a string instead.
+/// Use [bit_string](bit_string/index.html) to write '0' and '1'.
+///
+/// # Examples
+/// ```
+/// use fid_rs::Fid;
+///
+/// let fid = Fid::from("0100_1");
+/// assert_eq!(serde_json::to_string(&fid).unwrap(), r#""5:48""#);
+/// assert_eq!(serde_json::from_str::<Fid>(r#""0100_1""#).unwrap(), fid);
+/// ```
+impl Serialize for Fid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_hex())
+        } else {
+            CompactFidRef {
+                bit_len: self.bit_len,
+                bit_order: self.bit_order,
+                bytes: Bytes(&self.byte_vec),
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+/// Inverse of `Serialize`. Human-readable formats also accept a string of '0' and '1' (with '_' ignored).
+///
+/// Fails when the bit length is _0_, the bit length and the number of bytes are inconsistent,
+/// or unused bits in the last byte are not _0_. Hence a deserialized `Fid` always passes [validate()](#method.validate).
+impl<'de> Deserialize<'de> for Fid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(TextVisitor)
+        } else {
+            let CompactFid {
+                bit_len,
+                bit_order,
+                bytes,
+            } = CompactFid::deserialize(deserializer)?;
+            Fid::try_from_raw_parts(bytes.0, bit_len, bit_order).map_err(de::Error::custom)
+        }
+    }
+}
+
+/// Serializes a `Fid` as a string of '0' and '1' (`Display` of `Fid`) in any format,
+/// by `#[serde(with = "fid_rs::fid::bit_string")]`.
+///
+/// Deserialization also accepts `"<bit length>:<hex>"` made by `Fid::to_hex()`.
+///
+/// # Examples
+/// ```
+/// use fid_rs::Fid;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "fid_rs::fid::bit_string")]
+///     mask: Fid,
+/// }
+///
+/// let config = Config { mask: Fid::from("0100_1") };
+/// assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"mask":"01001"}"#);
+/// ```
+pub mod bit_string {
+    use super::TextVisitor;
+    use crate::Fid;
+    use serde::{Deserializer, Serializer};
+
+    /// Writes `Display` of `fid`.
+    pub fn serialize<S: Serializer>(fid: &Fid, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(fid)
+    }
+
+    /// Parses a string of '0' and '1' (with '_' ignored) or `"<bit length>:<hex>"`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fid, D::Error> {
+        deserializer.deserialize_str(TextVisitor)
+    }
+}
+
+#[cfg(test)]
+mod serde_success_tests {
+    use crate::{BitOrder, Fid};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        #[serde(with = "crate::fid::bit_string")]
+        mask: Fid,
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_fid, expected_json): (Fid, &str) = $value;
+
+                let bytes = bincode::serialize(&in_fid).unwrap();
+                // bit_len (u64), bit_order (u32), length of bytes (u64) and bytes.
+                assert_eq!(bytes.len() as u64, 8 + 4 + 8 + in_fid.len().div_ceil(8));
+                let fid: Fid = bincode::deserialize(&bytes).unwrap();
+                assert_eq!(fid, in_fid);
+                assert_eq!(fid.bit_order(), in_fid.bit_order());
+                assert_eq!(fid.validate(), Ok(()));
+                assert_eq!(fid.rank(fid.len() - 1), in_fid.rank(in_fid.len() - 1));
+
+                let json = serde_json::to_string(&in_fid).unwrap();
+                assert_eq!(json, expected_json);
+                assert_eq!(serde_json::from_str::<Fid>(&json).unwrap(), in_fid);
+
+                let bit_string = format!("\"{}\"", in_fid);
+                assert_eq!(serde_json::from_str::<Fid>(&bit_string).unwrap(), in_fid);
+
+                let config = Config { mask: in_fid.clone() };
+                let config_json = serde_json::to_string(&config).unwrap();
+                assert_eq!(config_json, format!("{{\"mask\":{}}}", bit_string));
+                assert_eq!(serde_json::from_str::<Config>(&config_json).unwrap(), config);
+                assert_eq!(
+                    serde_json::from_str::<Config>(&format!("{{\"mask\":{}}}", json)).unwrap(),
+                    config
+                );
+                let config_bytes = bincode::serialize(&config).unwrap();
+                assert_eq!(bincode::deserialize::<Config>(&config_bytes).unwrap(), config);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (Fid::from("0"), r#""1:00""#),
+        t2: (Fid::from("1"), r#""1:80""#),
+        t3: (Fid::from("0100_1"), r#""5:48""#),
+        t4: (Fid::from_raw_parts(vec![0b0001_0010, 0xff], 13, BitOrder::LsbFirst), r#""13:48f8""#),
+        t5: (Fid::from(&[true; 100][..]), r#""100:fffffffffffffffffffffffff0""#),
+    }
+
+    #[test]
+    fn smaller_than_bits_and_index() {
+        let fid = Fid::from(&[true; 1 << 16][..]);
+        let bytes = bincode::serialize(&fid).unwrap();
+        assert_eq!(bytes.len(), 20 + (1 << 13));
+    }
+}
+
+#[cfg(test)]
+mod serde_failure_tests {
+    use crate::Fid;
+
+    fn compact(bit_len: u64, bit_order: u32, bytes: &[u8]) -> Vec<u8> {
+        let mut buf = bit_len.to_le_bytes().to_vec();
+        buf.extend_from_slice(&bit_order.to_le_bytes());
+        buf.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+        buf.extend_from_slice(bytes);
+        buf
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_bytes, expected_message): (Vec<u8>, &str) = $value;
+                let err = bincode::deserialize::<Fid>(&in_bytes).unwrap_err();
+                assert!(err.to_string().contains(expected_message), "{}", err);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (compact(0, 0, &[]), "bit length must be greater than 0"),
+        t2: (compact(5, 0, &[]), "byte length inconsistent with bit length"),
+        t3: (compact(5, 0, &[0x48, 0]), "byte length inconsistent with bit length"),
+        t4: (compact(9, 0, &[0x48]), "byte length inconsistent with bit length"),
+        t5: (compact(5, 0, &[0x4c]), "bits after bit length 5 must be 0"),
+        t6: (compact(5, 1, &[0x32]), "bits after bit length 5 must be 0"),
+        t7: (compact(5, 2, &[0x48]), "variant index"),
+        t8: (compact(5, 0, &[0x48])[..20].to_vec(), "io error"),
+    }
+
+    macro_rules! parameterized_json_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_json, expected_message): (&str, &str) = $value;
+                let err = serde_json::from_str::<Fid>(in_json).unwrap_err();
+                assert!(err.to_string().contains(expected_message), "{}", err);
+            }
+        )*
+        }
+    }
+
+    parameterized_json_tests! {
+        json_t1: (r#""""#, "bit length must be greater than 0"),
+        json_t2: (r#""___""#, "bit length must be greater than 0"),
+        json_t3: (r#""01x1""#, "unexpected character 'x' at byte 2"),
+        json_t4: (r#""5:4""#, "expected 2 characters after bit length but got 1"),
+        json_t5: (r#""5:4c""#, "bits after bit length 5 must be 0"),
+        json_t6: (r#"[72]"#, "invalid type"),
+    }
+}
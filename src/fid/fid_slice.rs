@@ -0,0 +1,321 @@
+use super::{Fid, FidSlice, FidSliceIter, Ones};
+use core::ops::{Bound, RangeBounds};
+
+impl Fid {
+    /// Returns a view of `range` of this FID, which answers queries relative to the start of `range`.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// // 3 documents concatenated: "0100", "1", "1011".
+    /// let fid = Fid::from("0100_1_1011");
+    /// let doc = fid.slice(5..9);
+    /// assert_eq!(doc.len(), 4);
+    /// assert_eq!(doc.rank(2), 2);
+    /// assert_eq!(doc.select1(2), Some(3));
+    /// assert_eq!(doc.ones().collect::<Vec<u64>>(), vec![0, 2, 3]);
+    /// ```
+    ///
+    /// # Panics
+    /// When:
+    /// - start of `range` > end of `range`.
+    /// - end of `range` > length of the `Fid`.
+    pub fn slice<R: RangeBounds<u64>>(&self, range: R) -> FidSlice<'_> {
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i.checked_add(1).unwrap_or_else(|| {
+                panic!(
+                    "range end index {} + 1 out of range for Fid of length {}",
+                    i,
+                    self.len()
+                )
+            }),
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len(),
+        };
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i
+                .checked_add(1)
+                .unwrap_or_else(|| panic!("slice index starts at {} + 1 but ends at {}", i, end)),
+            Bound::Unbounded => 0,
+        };
+        assert!(
+            start <= end,
+            "slice index starts at {} but ends at {}",
+            start,
+            end
+        );
+        assert!(
+            end <= self.len(),
+            "range end index {} out of range for Fid of length {}",
+            end,
+            self.len()
+        );
+
+        FidSlice {
+            fid: self,
+            start,
+            len: end - start,
+            rank_before_start: if start == 0 { 0 } else { self.rank(start - 1) },
+        }
+    }
+}
+
+impl<'fid> FidSlice<'fid> {
+    /// Returns bit length of this slice.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether this slice is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `i`-th bit of this slice.
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn get(&self, i: u64) -> bool {
+        assert!(
+            i < self.len,
+            "index {} out of range for length {}",
+            i,
+            self.len
+        );
+        self.fid[self.start + i]
+    }
+
+    /// Returns the number of _1_ in _[0, `i`]_ elements of this slice.
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn rank(&self, i: u64) -> u64 {
+        assert!(
+            i < self.len,
+            "index {} out of range for length {}",
+            i,
+            self.len
+        );
+        self.fid.rank(self.start + i) - self.rank_before_start
+    }
+
+    /// Returns the number of _0_ in _[0, `i`]_ elements of this slice.
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn rank0(&self, i: u64) -> u64 {
+        (i + 1) - self.rank(i)
+    }
+
+    /// Returns the number of _1_ in this slice.
+    pub fn count_ones(&self) -> u64 {
+        if self.len == 0 {
+            0
+        } else {
+            self.rank(self.len - 1)
+        }
+    }
+
+    /// Returns the position (0-origin) of the `k`-th (0-origin) _1_ in this slice if exists. Else returns None.
+    pub fn select1(&self, k: u64) -> Option<u64> {
+        if k >= self.count_ones() {
+            return None;
+        }
+        self.fid
+            .select1(self.rank_before_start + k)
+            .map(|i| i - self.start)
+    }
+
+    /// Returns the position (0-origin) of the `k`-th (0-origin) _0_ in this slice if exists. Else returns None.
+    pub fn select0(&self, k: u64) -> Option<u64> {
+        if k >= self.len - self.count_ones() {
+            return None;
+        }
+        let zeros_before_start = self.start - self.rank_before_start;
+        self.fid
+            .select0(zeros_before_start + k)
+            .map(|i| i - self.start)
+    }
+
+    /// Creates an iterator over bits of this slice.
+    pub fn iter(&self) -> FidSliceIter<'fid> {
+        FidSliceIter { slice: *self, i: 0 }
+    }
+
+    /// Creates an iterator over positions of _1_ in this slice, in ascending order.
+    ///
+    /// Scans 64 bits at a time.
+    pub fn ones(&self) -> Ones<'fid> {
+        let (i_word, word) = if self.len == 0 {
+            (0, 0)
+        } else {
+            let i_word = self.start / 64;
+            let word = self.fid.msb_word(i_word, self.start + self.len);
+            (i_word, word & (u64::MAX >> (self.start % 64)))
+        };
+        Ones {
+            slice: *self,
+            i_word,
+            word,
+        }
+    }
+}
+
+impl Iterator for FidSliceIter<'_> {
+    type Item = bool;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i >= self.slice.len() {
+            None
+        } else {
+            self.i += 1;
+            Some(self.slice.get(self.i - 1))
+        }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = u64;
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.slice.start + self.slice.len;
+        while self.word == 0 {
+            self.i_word += 1;
+            if self.slice.len == 0 || self.i_word * 64 >= end {
+                return None;
+            }
+            self.word = self.slice.fid.msb_word(self.i_word, end);
+        }
+
+        let lz = self.word.leading_zeros() as u64;
+        self.word &= !((1 << 63) >> lz);
+        Some(self.i_word * 64 + lz - self.slice.start)
+    }
+}
+
+#[cfg(test)]
+mod slice_success_tests {
+    use crate::Fid;
+    use alloc::vec::Vec;
+
+    fn sample_bits(len: usize) -> Vec<bool> {
+        (0..len).map(|i| i % 3 == 0 || i % 7 == 0).collect()
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_len, in_start, in_end) = $value;
+                let bits = sample_bits(in_len);
+                let fid = Fid::from(&bits[..]);
+                let slice = fid.slice(in_start..in_end);
+                let expected_bits = &bits[in_start as usize..in_end as usize];
+                let expected = Fid::from(expected_bits);
+
+                assert_eq!(slice.len(), expected.len());
+                assert_eq!(slice.iter().collect::<Vec<bool>>(), expected_bits);
+                for i in 0..slice.len() {
+                    assert_eq!(slice.get(i), expected[i], "i = {}", i);
+                    assert_eq!(slice.rank(i), expected.rank(i), "i = {}", i);
+                    assert_eq!(slice.rank0(i), expected.rank0(i), "i = {}", i);
+                }
+                for k in 0..=slice.len() {
+                    assert_eq!(slice.select1(k), expected.select1(k), "k = {}", k);
+                    assert_eq!(slice.select0(k), expected.select0(k), "k = {}", k);
+                }
+                let expected_ones: Vec<u64> = (0..expected.len()).filter(|&i| expected[i]).collect();
+                assert_eq!(slice.ones().collect::<Vec<u64>>(), expected_ones);
+                assert_eq!(slice.count_ones(), expected_ones.len() as u64);
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (1, 0, 1),
+        t2: (5, 0, 5),
+        t3: (5, 1, 4),
+        t4: (5, 4, 5),
+        t5: (64, 0, 64),
+        t6: (64, 63, 64),
+        t7: (65, 63, 65),
+        t8: (200, 1, 199),
+        t9: (200, 64, 128),
+        t10: (200, 70, 71),
+        t11: (1000, 123, 877),
+    }
+
+    #[test]
+    fn range_bounds() {
+        let fid = Fid::from("0100_1");
+        assert_eq!(fid.slice(..).len(), 5);
+        assert_eq!(fid.slice(1..).len(), 4);
+        assert_eq!(fid.slice(..=1).len(), 2);
+        assert_eq!(fid.slice(1..=1).iter().collect::<Vec<bool>>(), [true]);
+    }
+
+    #[test]
+    fn empty() {
+        let fid = Fid::from("0100_1");
+        for i in 0..=fid.len() {
+            let slice = fid.slice(i..i);
+            assert!(slice.is_empty());
+            assert_eq!(slice.iter().count(), 0);
+            assert_eq!(slice.ones().count(), 0);
+            assert_eq!(slice.count_ones(), 0);
+            assert_eq!(slice.select1(0), None);
+            assert_eq!(slice.select0(0), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod slice_failure_tests {
+    use crate::Fid;
+    use core::ops::Bound;
+
+    #[test]
+    #[should_panic]
+    fn end_over_len() {
+        let fid = Fid::from("0100_1");
+        let _ = fid.slice(1..6);
+    }
+
+    #[test]
+    #[should_panic]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn start_over_end() {
+        let fid = Fid::from("0100_1");
+        let _ = fid.slice(3..2);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn end_included_max() {
+        let fid = Fid::from("0100_1");
+        let _ = fid.slice(..=u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice index starts at")]
+    fn start_excluded_max() {
+        let fid = Fid::from("0100_1");
+        let _ = fid.slice((Bound::Excluded(u64::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_over_len() {
+        let fid = Fid::from("0100_1");
+        let _ = fid.slice(1..3).get(2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rank_over_len() {
+        let fid = Fid::from("0100_1");
+        let _ = fid.slice(1..3).rank(2);
+    }
+}
@@ -0,0 +1,90 @@
+use super::fid_impl::check_parts;
+use super::{Fid, FidError};
+
+impl Fid {
+    /// Checks the integrity of the bits and the index, by recomputing the index in _O(N)_.
+    ///
+    /// Every constructor and deserialization make a valid `Fid`, so this is only for a `Fid`
+    /// which may have been damaged in memory or in an unchecked storage.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// assert_eq!(Fid::from("0100_1").validate(), Ok(()));
+    /// ```
+    ///
+    /// # Errors
+    /// - `FidError::ZeroLength` when the bit length is _0_.
+    /// - `FidError::Corrupted` when the number of bytes is inconsistent with the bit length,
+    ///   or the index is inconsistent with the bits.
+    /// - `FidError::NonZeroPaddingBits` when an unused bit in the last byte is _1_.
+    pub fn validate(&self) -> Result<(), FidError> {
+        check_parts(
+            &self.byte_vec,
+            self.bit_len,
+            self.bit_order,
+            &self.chunks,
+            &self.table,
+        )
+    }
+}
+
+#[cfg(test)]
+mod validate_success_tests {
+    use crate::{BitOrder, Fid};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let fid: Fid = $value;
+                assert_eq!(fid.validate(), Ok(()));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: Fid::from("0"),
+        t2: Fid::from("0100_1"),
+        t3: Fid::from(&[true; 1000][..]),
+        t4: Fid::from_raw_parts(vec![0b0001_0010, 0xff], 13, BitOrder::LsbFirst),
+        t5: Fid::from_runs(true, vec![100, 1000, 10, 3000]).unwrap(),
+        t6: Fid::from_hex("10:4840").unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod validate_failure_tests {
+    use crate::internal_data_structure::popcount_table::PopcountTable;
+    use crate::{BitOrder, Fid, FidError};
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_corrupt, expected_error): (fn(&mut Fid), FidError) = $value;
+                let mut fid = Fid::from_runs(true, vec![100, 1000, 10, 3003]).unwrap();
+                in_corrupt(&mut fid);
+                assert_eq!(fid.validate(), Err(expected_error));
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (|fid| fid.bit_len = 0, FidError::ZeroLength),
+        t2: (|fid| fid.bit_len += 8, FidError::Corrupted { reason: "byte length inconsistent with bit length" }),
+        t3: (|fid| { fid.byte_vec.pop(); }, FidError::Corrupted { reason: "byte length inconsistent with bit length" }),
+        t4: (|fid| *fid.byte_vec.last_mut().unwrap() |= 1, FidError::NonZeroPaddingBits { bit_len: 4113 }),
+        t5: (|fid| { *fid.byte_vec.last_mut().unwrap() = 0x80; fid.bit_order = BitOrder::LsbFirst; }, FidError::NonZeroPaddingBits { bit_len: 4113 }),
+        t6: (|fid| fid.byte_vec[0] ^= 0x10, FidError::Corrupted { reason: "rank directory inconsistent with bits" }),
+        t7: (|fid| fid.chunks.chunks[3] += 1, FidError::Corrupted { reason: "rank directory inconsistent with bits" }),
+        t8: (|fid| fid.chunks.blocks[5] -= 1, FidError::Corrupted { reason: "rank directory inconsistent with bits" }),
+        t9: (|fid| { fid.chunks.blocks.pop(); }, FidError::Corrupted { reason: "rank directory inconsistent with bits" }),
+        t10: (|fid| fid.table = PopcountTable::new(3), FidError::Corrupted { reason: "popcount table inconsistent with bit length" }),
+    }
+}
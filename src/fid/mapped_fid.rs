@@ -0,0 +1,268 @@
+use super::fid_impl::{rank_by, select_by};
+use super::{BitOrder, Chunks, Fid, FidError, MappedFid, MappedFidLayout};
+use crate::internal_data_structure::raw_bit_vector::RawBitVector;
+
+/// Magic number at the beginning of a FID file.
+pub(super) const MAGIC: [u8; 8] = *b"FIDRSMAP";
+
+/// Version of the FID file format.
+pub(super) const VERSION: u32 = 1;
+
+/// Length of the header: `MAGIC`, `VERSION` (u32), reserved (u32) and bit length (u64).
+pub(super) const HEADER_LEN: u64 = 24;
+
+/// A FID file (all integers are little endian) consists of:
+///
+/// 1. Header (`HEADER_LEN` bytes).
+/// 2. Packed bits in `BitOrder::MsbFirst`, padded with _0_ to a multiple of 8 bytes.
+/// 3. Chunk directory: `u64` per chunk, the number of _1_ up to the end of the chunk.
+/// 4. Block directory: `u16` per block, the number of _1_ from the start of its chunk to the end of the block.
+///    Blocks do not cross chunks, so the last block of a chunk may be shorter than the block size.
+///
+/// Chunk size and block size are the same as `Fid`.
+impl MappedFidLayout {
+    /// # Panics
+    /// When _`bit_len` == 0_.
+    pub(super) fn new(bit_len: u64) -> Self {
+        assert!(bit_len > 0);
+        let chunk_size = Chunks::calc_chunk_size(bit_len) as u64;
+        let block_size = Chunks::calc_block_size(bit_len) as u64;
+        let chunks_cnt = bit_len.div_ceil(chunk_size);
+        let blocks_per_chunk = chunk_size.div_ceil(block_size);
+        let last_chunk_len = bit_len - (chunks_cnt - 1) * chunk_size;
+        let blocks_cnt = (chunks_cnt - 1) * blocks_per_chunk + last_chunk_len.div_ceil(block_size);
+
+        let chunks_offset = HEADER_LEN + bit_len.div_ceil(64) * 8;
+        let blocks_offset = chunks_offset + chunks_cnt * 8;
+        MappedFidLayout {
+            bit_len,
+            chunk_size,
+            block_size,
+            blocks_per_chunk,
+            chunks_offset,
+            blocks_offset,
+            file_len: blocks_offset + blocks_cnt * 2,
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> MappedFid<B> {
+    /// Constructor from the whole content of a FID file written by [ExternalBuilder](struct.ExternalBuilder.html).
+    ///
+    /// Only the header and the length of `bytes` are checked, in _O(1)_.
+    ///
+    /// # Errors
+    /// `FidError::Corrupted` when `bytes` has a wrong header or length.
+    pub fn from_bytes(bytes: B) -> Result<Self, FidError> {
+        let buf = bytes.as_ref();
+        if (buf.len() as u64) < HEADER_LEN {
+            return Err(FidError::Corrupted {
+                reason: "shorter than header",
+            });
+        }
+        if buf[0..8] != MAGIC {
+            return Err(FidError::Corrupted {
+                reason: "not a FID file",
+            });
+        }
+        if read_u32(buf, 8) != VERSION {
+            return Err(FidError::Corrupted {
+                reason: "unsupported version",
+            });
+        }
+        let bit_len = read_u64(buf, 16);
+        if bit_len == 0 || bit_len.div_ceil(8) > buf.len() as u64 - HEADER_LEN {
+            return Err(FidError::Corrupted {
+                reason: "bit length inconsistent with file length",
+            });
+        }
+        let layout = MappedFidLayout::new(bit_len);
+        if layout.file_len != buf.len() as u64 {
+            return Err(FidError::Corrupted {
+                reason: "bit length inconsistent with file length",
+            });
+        }
+        Ok(MappedFid { bytes, layout })
+    }
+
+    /// Returns bit length.
+    pub fn len(&self) -> u64 {
+        self.layout.bit_len
+    }
+
+    /// Returns whether empty. Always `false`, since a FID file has at least 1 bit.
+    pub fn is_empty(&self) -> bool {
+        self.layout.bit_len == 0
+    }
+
+    /// Returns the packed bits in `BitOrder::MsbFirst`, _ceil(`len()` / 8)_ bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        let start = HEADER_LEN as usize;
+        &self.bytes.as_ref()[start..start + self.layout.bit_len.div_ceil(8) as usize]
+    }
+
+    /// Returns `i`-th bit.
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn get(&self, i: u64) -> bool {
+        assert!(i < self.len());
+        self.as_bytes()[(i / 8) as usize] & (0x80 >> (i % 8)) != 0
+    }
+
+    /// Returns the number of _1_ in _[0, `i`]_ elements.
+    ///
+    /// Reads a chunk entry, a block entry and at most 5 bytes of bits.
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn rank(&self, i: u64) -> u64 {
+        let buf = self.bytes.as_ref();
+        let layout = &self.layout;
+        rank_by(
+            &self.rbv(),
+            i,
+            |i_chunk| read_u64(buf, layout.chunks_offset + i_chunk * 8),
+            |i_block| read_u16(buf, layout.blocks_offset + i_block * 2),
+            |block_bits| block_bits.count_ones() as u8,
+        )
+    }
+
+    /// Returns the number of _0_ in _[0, `i`]_ elements.
+    ///
+    /// # Panics
+    /// When _`i` >= `self.len()`_.
+    pub fn rank0(&self, i: u64) -> u64 {
+        (i + 1) - self.rank(i)
+    }
+
+    /// Returns the position (0-origin) of the `k`-th (0-origin) _1_ if exists. Else returns None.
+    pub fn select1(&self, k: u64) -> Option<u64> {
+        select_by(self.len(), k, |i| self.rank(i))
+    }
+
+    /// Returns the position (0-origin) of the `k`-th (0-origin) _0_ if exists. Else returns None.
+    pub fn select0(&self, k: u64) -> Option<u64> {
+        select_by(self.len(), k, |i| self.rank0(i))
+    }
+
+    /// Loads the bits into memory as a `Fid`.
+    pub fn to_fid(&self) -> Fid {
+        Fid::from_raw_parts(self.as_bytes().to_vec(), self.len(), BitOrder::MsbFirst)
+    }
+
+    /// Returns the underlying bytes.
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+
+    fn rbv(&self) -> RawBitVector<'_> {
+        let last_byte_len = ((self.len() - 1) % 8 + 1) as u8;
+        RawBitVector::new(self.as_bytes(), 0, last_byte_len)
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl MappedFid<memmap2::Mmap> {
+    /// Memory-maps a FID file written by [ExternalBuilder](struct.ExternalBuilder.html).
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while the returned `MappedFid` is alive.
+    /// See `memmap2::Mmap::map()`.
+    ///
+    /// # Errors
+    /// - `FidError::Io` when the file cannot be opened or mapped.
+    /// - `FidError::Corrupted` when the file has a wrong header or length.
+    pub unsafe fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, FidError> {
+        let file = std::fs::File::open(path)?;
+        let mmap = memmap2::Mmap::map(&file)?;
+        MappedFid::from_bytes(mmap)
+    }
+}
+
+fn read_u16(buf: &[u8], offset: u64) -> u16 {
+    let offset = offset as usize;
+    u16::from_le_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn read_u32(buf: &[u8], offset: u64) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buf[offset as usize..offset as usize + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+fn read_u64(buf: &[u8], offset: u64) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[offset as usize..offset as usize + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod mapped_fid_failure_tests {
+    use crate::{ExternalBuilder, FidError, MappedFid, ReadFormat};
+    use std::io::Cursor;
+
+    fn build(text: &str) -> Vec<u8> {
+        let mut file = Cursor::new(Vec::new());
+        ExternalBuilder::new()
+            .build(Cursor::new(text), ReadFormat::Text, &mut file)
+            .unwrap();
+        file.into_inner()
+    }
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (in_modify, expected_reason): (fn(&mut Vec<u8>), &str) = $value;
+                let mut bytes = build("0100_1");
+                in_modify(&mut bytes);
+                assert_eq!(
+                    MappedFid::from_bytes(bytes).unwrap_err(),
+                    FidError::Corrupted { reason: expected_reason }
+                );
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (|bytes| bytes.truncate(23), "shorter than header"),
+        t2: (|bytes| bytes[0] = b'X', "not a FID file"),
+        t3: (|bytes| bytes[8] = 2, "unsupported version"),
+        t4: (|bytes| bytes[16..24].copy_from_slice(&0u64.to_le_bytes()), "bit length inconsistent with file length"),
+        t5: (|bytes| bytes[16..24].copy_from_slice(&u64::MAX.to_le_bytes()), "bit length inconsistent with file length"),
+        t6: (|bytes| bytes[16..24].copy_from_slice(&100u64.to_le_bytes()), "bit length inconsistent with file length"),
+        t7: (|bytes| bytes.push(0), "bit length inconsistent with file length"),
+        t8: (|bytes| { bytes.pop(); }, "bit length inconsistent with file length"),
+    }
+}
+
+#[cfg(all(test, feature = "mmap"))]
+mod mmap_success_tests {
+    use crate::{ExternalBuilder, Fid, MappedFid, ReadFormat};
+    use std::fs::File;
+    use std::io::Cursor;
+
+    #[test]
+    fn open() {
+        let fid = Fid::from_runs(true, vec![100, 1000, 10, 3000]).unwrap();
+        let path = std::env::temp_dir().join(format!("fid-rs-mmap-{}.fid", std::process::id()));
+        ExternalBuilder::new()
+            .build(
+                Cursor::new(format!("{}", fid)),
+                ReadFormat::Text,
+                File::create(&path).unwrap(),
+            )
+            .unwrap();
+
+        let mapped = unsafe { MappedFid::open(&path) }.unwrap();
+        assert_eq!(mapped.to_fid(), fid);
+        for i in (0..fid.len()).step_by(7) {
+            assert_eq!(mapped.rank(i), fid.rank(i), "i = {}", i);
+        }
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+use super::{Fid, SpaceReport};
+use core::mem;
+
+impl Fid {
+    /// Returns bytes allocated on heap by this FID.
+    ///
+    /// Equals to `self.space_report().total()`.
+    pub fn heap_size(&self) -> usize {
+        self.space_report().total()
+    }
+
+    /// Returns bytes used by this FID, including the `Fid` struct itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use fid_rs::Fid;
+    ///
+    /// let fid = Fid::from("0100_1");
+    /// assert_eq!(fid.size_in_bytes(), std::mem::size_of::<Fid>() + fid.heap_size());
+    /// ```
+    pub fn size_in_bytes(&self) -> usize {
+        mem::size_of::<Fid>() + self.heap_size()
+    }
+
+    /// Returns per-component breakdown of heap memory used by this FID.
+    pub fn space_report(&self) -> SpaceReport {
+        SpaceReport {
+            bit_len: self.bit_len,
+            raw_bits: self.byte_vec.capacity(),
+            chunk_directory: self.chunks.chunks_heap_size(),
+            block_directory: self.chunks.blocks_heap_size(),
+            popcount_table: self.table.heap_size(),
+            select_samples: 0,
+        }
+    }
+}
+
+impl SpaceReport {
+    /// Returns total bytes of all the components.
+    pub fn total(&self) -> usize {
+        self.raw_bits + self.index_size()
+    }
+
+    /// Returns bytes used by everything other than the raw bit vector.
+    pub fn index_size(&self) -> usize {
+        self.chunk_directory + self.block_directory + self.popcount_table + self.select_samples
+    }
+
+    /// Returns how many bits of index are used per bit of the FID.
+    ///
+    /// _0.0_ means no overhead. Returns _0.0_ for an empty FID.
+    pub fn overhead_bits_per_bit(&self) -> f64 {
+        if self.bit_len == 0 {
+            0.0
+        } else {
+            (self.index_size() as f64 * 8.0) / self.bit_len as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod space_report_success_tests {
+    use crate::Fid;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (bits, expected_raw_bits, expected_chunk_directory, expected_block_directory, expected_popcount_table):
+                    (Vec<bool>, usize, usize, usize, usize) = $value;
+                let fid = Fid::from(&bits[..]);
+                let report = fid.space_report();
+
+                assert_eq!(report.bit_len, bits.len() as u64);
+                assert_eq!(report.raw_bits, expected_raw_bits);
+                assert_eq!(report.chunk_directory, expected_chunk_directory);
+                assert_eq!(report.block_directory, expected_block_directory);
+                assert_eq!(report.popcount_table, expected_popcount_table);
+                assert_eq!(report.select_samples, 0);
+                assert_eq!(
+                    report.index_size(),
+                    expected_chunk_directory + expected_block_directory + expected_popcount_table
+                );
+                assert_eq!(report.total(), expected_raw_bits + report.index_size());
+                assert_eq!(fid.heap_size(), report.total());
+                assert_eq!(
+                    report.overhead_bits_per_bit(),
+                    (report.index_size() * 8) as f64 / bits.len() as f64
+                );
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        // (bits, raw_bits, chunk_directory, block_directory, popcount_table)
+        t1: (vec![true], 1, 8, 2, 2),
+        t2: (vec![false, true], 1, 16, 4, 2),
+        // chunk: 36 bits * 3, block: 3 bits * (12 + 12 + 10)
+        t100: (vec![true; 100], 13, 3 * 8, 34 * 2, 1 << 3),
+        // chunk: 81 bits * 13, block: 4 bits * (21 * 12 + 7)
+        t1000: (vec![false; 1000], 126, 13 * 8, 259 * 2, 1 << 4),
+        // chunk: 256 bits * 256, block: 8 bits * (32 * 256)
+        t65536: (vec![true; 1 << 16], 8193, 256 * 8, 8192 * 2, 1 << 8),
+    }
+}
+
+#[cfg(test)]
+mod space_report_failure_tests {
+    // Nothing to test
+}
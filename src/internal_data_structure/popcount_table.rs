@@ -1,13 +1,16 @@
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use alloc::vec::Vec;
 
 #[cfg(feature = "mem_dbg")]
 use mem_dbg::{MemDbg, MemSize};
 
 /// Cache table of `popcount` results.
-#[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
 pub struct PopcountTable {
     bit_length: u8,
 
@@ -53,6 +56,22 @@ impl PopcountTable {
 
         self.table[target as usize]
     }
+
+    /// Returns bytes allocated for the table.
+    pub fn heap_size(&self) -> usize {
+        self.table.capacity()
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedPopcountTable {
+    /// Same as `PopcountTable::popcount()`.
+    ///
+    /// # Panics
+    /// When `target` is out of the table.
+    pub fn popcount(&self, target: u64) -> u8 {
+        self.table[target as usize]
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,6 @@
-use std::fmt;
+use crate::fid::BitOrder;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
 
 #[derive(Debug)]
 /// Bit vector of arbitrary length (actually the length is limited to _[1, 2^64)_).
@@ -9,6 +11,9 @@ use std::fmt;
 /// 10101010 00000000 11111111
 ///   |  effective bits |
 /// ```
+///
+/// Figures in this file are drawn in `BitOrder::MsbFirst`.
+/// With `BitOrder::LsbFirst`, each byte is bit-reversed on read, so that the same logic works.
 pub struct RawBitVector<'s> {
     byte_slice: &'s [u8],
     first_byte_offset: u8,
@@ -17,6 +22,9 @@ pub struct RawBitVector<'s> {
     /// Although byte_slice has only 1 byte and first_byte_offset > 0,
     /// this var can take up to 8.
     last_byte_len: u8,
+
+    /// Bit order in each byte of `byte_slice`.
+    bit_order: BitOrder,
 }
 
 impl<'s> RawBitVector<'s> {
@@ -37,6 +45,21 @@ impl<'s> RawBitVector<'s> {
             byte_slice,
             first_byte_offset,
             last_byte_len,
+            bit_order: BitOrder::MsbFirst,
+        }
+    }
+
+    /// Sets the bit order of bytes. Defaults to `BitOrder::MsbFirst`.
+    pub fn with_bit_order(self, bit_order: BitOrder) -> Self {
+        Self { bit_order, ..self }
+    }
+
+    /// Returns `i`-th byte in `BitOrder::MsbFirst`.
+    fn byte(&self, i: usize) -> u8 {
+        let byte = self.byte_slice[i];
+        match self.bit_order {
+            BitOrder::MsbFirst => byte,
+            BitOrder::LsbFirst => byte.reverse_bits(),
         }
     }
 
@@ -63,7 +86,7 @@ impl<'s> RawBitVector<'s> {
         assert!(i < self.len());
 
         let abs_i = self.first_byte_offset as u64 + i;
-        let byte = self.byte_slice[(abs_i / 8) as usize];
+        let byte = self.byte((abs_i / 8) as usize);
         match abs_i % 8 {
             0 => byte & 0b1000_0000 != 0,
             1 => byte & 0b0100_0000 != 0,
@@ -88,44 +111,29 @@ impl<'s> RawBitVector<'s> {
         }
     }
 
-    /// Returns popcount of whole this bit vector.
-    pub fn popcount(&self) -> u64 {
-        let mut popcnt = self
-            .byte_slice
-            .iter()
-            .fold(0, |popcnt: u64, byte| byte.count_ones() as u64 + popcnt);
-
-        // remove 1s in the left of first_byte_offset
-        let left_1s_byte = match self.first_byte_offset {
-            0 => 0,
-            1 => 0b10000000 & self.byte_slice[0],
-            2 => 0b11000000 & self.byte_slice[0],
-            3 => 0b11100000 & self.byte_slice[0],
-            4 => 0b11110000 & self.byte_slice[0],
-            5 => 0b11111000 & self.byte_slice[0],
-            6 => 0b11111100 & self.byte_slice[0],
-            7 => 0b11111110 & self.byte_slice[0],
-            _ => panic!("never happen"),
-        };
-        popcnt -= left_1s_byte.count_ones() as u64;
-
-        // remove 1s in the left of last_byte_len
-        let last_byte = self.byte_slice.last().unwrap();
-        let last_offset = self.last_byte_len - 1;
-        let right_1s_byte = match last_offset {
-            0 => 0b01111111 & last_byte,
-            1 => 0b00111111 & last_byte,
-            2 => 0b00011111 & last_byte,
-            3 => 0b00001111 & last_byte,
-            4 => 0b00000111 & last_byte,
-            5 => 0b00000011 & last_byte,
-            6 => 0b00000001 & last_byte,
-            7 => 0,
-            _ => panic!("never happen"),
-        };
-        popcnt -= right_1s_byte.count_ones() as u64;
+    /// Returns popcount of _[`i`, `i` + `size`)_, reading 8 bytes at once.
+    ///
+    /// # Panics
+    /// When:
+    /// - _`size` > 56_
+    /// - _`i` + `size` > `self.len()`_
+    pub fn popcount_range(&self, i: u64, size: u64) -> u64 {
+        assert!(size <= 56);
+        assert!(i + size <= self.len());
+        if size == 0 {
+            return 0;
+        }
 
-        popcnt
+        let abs_i = self.first_byte_offset as u64 + i;
+        let i_byte = (abs_i / 8) as usize;
+        let mut bytes = [0u8; 8];
+        let bytes_len = (self.byte_slice.len() - i_byte).min(8);
+        bytes[..bytes_len].copy_from_slice(&self.byte_slice[i_byte..i_byte + bytes_len]);
+        let word = match self.bit_order {
+            BitOrder::MsbFirst => u64::from_be_bytes(bytes),
+            BitOrder::LsbFirst => u64::from_le_bytes(bytes).reverse_bits(),
+        };
+        ((word << (abs_i % 8)) >> (64 - size)).count_ones() as u64
     }
 
     /// Makes another RawBitVector from _[`i`, `i` + `size`)_ of self.
@@ -179,6 +187,7 @@ impl<'s> RawBitVector<'s> {
             byte_slice: &self.byte_slice[(abs_i_start as usize / 8)..=(abs_i_end as usize / 8)],
             first_byte_offset: (abs_i_start % 8) as u8,
             last_byte_len: (abs_i_end % 8 + 1) as u8,
+            bit_order: self.bit_order,
         }
     }
 
@@ -194,8 +203,8 @@ impl<'s> RawBitVector<'s> {
 
         assert!(bs.len() <= 5);
         let mut a = [0u32; 5];
-        for i in 0..bs.len() {
-            a[i] = bs[i] as u32;
+        for (i, a_i) in a.iter_mut().enumerate().take(bs.len()) {
+            *a_i = self.byte(i) as u32;
         }
         // discard 1s in the last byte
         a[bs.len() - 1] = a[bs.len() - 1] >> (8 - self.last_byte_len) << (8 - self.last_byte_len);
@@ -215,8 +224,8 @@ impl<'s> fmt::Display for RawBitVector<'s> {
             .byte_slice
             .iter()
             .enumerate()
-            .map(|(i, byte)| {
-                let byte_s = format!("{: >8}", format!("{:b}", byte)).replace(' ', "0");
+            .map(|(i, _)| {
+                let byte_s = format!("{: >8}", format!("{:b}", self.byte(i))).replace(' ', "0");
                 if i < self.byte_slice.len() - 1 {
                     byte_s
                 } else {
@@ -391,7 +400,7 @@ mod access_failure_tests {
 }
 
 #[cfg(test)]
-mod popcount_success_tests {
+mod popcount_range_success_tests {
     use super::RawBitVector;
 
     macro_rules! parameterized_tests {
@@ -401,7 +410,7 @@ mod popcount_success_tests {
             fn $name() {
                 let (byte_slice, first_byte_offset, last_byte_len, expected_popcount) = $value;
                 let rbv = RawBitVector::new(byte_slice, first_byte_offset, last_byte_len);
-                assert_eq!(rbv.popcount(), expected_popcount);
+                assert_eq!(rbv.popcount_range(0, rbv.len()), expected_popcount);
             }
         )*
         }
@@ -424,7 +433,7 @@ mod popcount_success_tests {
 }
 
 #[cfg(test)]
-mod popcount_failure_tests {
+mod popcount_range_failure_tests {
     // Nothing to do
 }
 
@@ -553,7 +562,7 @@ mod clone_sub_fuzzing_tests {
         }
 
         fn str_into_byte_vec(s: &str) -> (Vec<u8>, u8) {
-            let bits: Vec<bool> = s.as_bytes().iter().map(|c| *c == '1' as u8).collect();
+            let bits: Vec<bool> = s.as_bytes().iter().map(|c| *c == b'1').collect();
 
             let mut byte_vec: Vec<u8> = Vec::with_capacity(bits.len() / 8 + 1);
             let mut last_byte_len = 0u8;
@@ -599,6 +608,50 @@ mod clone_sub_fuzzing_tests {
     }
 }
 
+#[cfg(test)]
+mod bit_order_success_tests {
+    use super::RawBitVector;
+    use crate::fid::BitOrder;
+    use alloc::vec::Vec;
+
+    macro_rules! parameterized_tests {
+        ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (byte_slice, first_byte_offset, last_byte_len): (&[u8], u8, u8) = $value;
+                let reversed: Vec<u8> = byte_slice.iter().map(|b| b.reverse_bits()).collect();
+
+                let msb = RawBitVector::new(byte_slice, first_byte_offset, last_byte_len);
+                let lsb = RawBitVector::new(&reversed[..], first_byte_offset, last_byte_len)
+                    .with_bit_order(BitOrder::LsbFirst);
+
+                assert_eq!(lsb.len(), msb.len());
+                assert_eq!(lsb.popcount_range(0, msb.len()), msb.popcount_range(0, msb.len()));
+                for i in 0..msb.len() {
+                    assert_eq!(lsb.access(i), msb.access(i), "i = {}", i);
+                    for size in 1..=(msb.len() - i).min(32) {
+                        let (msb_sub, lsb_sub) = (msb.clone_sub(i, size), lsb.clone_sub(i, size));
+                        assert_eq!(lsb.popcount_range(i, size), msb.popcount_range(i, size));
+                        assert_eq!(msb.popcount_range(i, size), msb_sub.as_u32().count_ones() as u64);
+                        assert_eq!(lsb_sub.as_u32(), msb_sub.as_u32());
+                    }
+                }
+            }
+        )*
+        }
+    }
+
+    parameterized_tests! {
+        t1: (&[0b1000_0000], 0, 1),
+        t2: (&[0b0110_1001], 0, 8),
+        t3: (&[0b0110_1001], 3, 7),
+        t4: (&[0b1011_0001, 0b0111_1100, 0b0000_0101], 0, 8),
+        t5: (&[0b1011_0001, 0b0111_1100, 0b0000_0101], 5, 3),
+        t6: (&[0b1011_0001, 0b0111_1100, 0b0000_0101, 0b1111_0000, 0b1010_1010, 0b0011_0011], 1, 6),
+    }
+}
+
 #[cfg(test)]
 mod as_u32_success_tests {
     use super::RawBitVector;
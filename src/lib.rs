@@ -1,6 +1,19 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-pub use fid::Fid;
+extern crate alloc;
 
+#[cfg(feature = "rkyv")]
+pub use fid::ArchivedFid;
+pub use fid::{BitOrder, Fid, FidError, FidSlice, MappedFid, SpaceReport};
+#[cfg(feature = "std")]
+pub use fid::{ExternalBuilder, ReadFormat};
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod fid;
 mod internal_data_structure;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
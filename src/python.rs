@@ -0,0 +1,226 @@
+//! Python bindings of [Fid](../fid/struct.Fid.html).
+//!
+//! Enabled by `python` feature. Build and install the extension module `fid_rs` with [maturin](https://www.maturin.rs/):
+//!
+//! ```text
+//! $ maturin develop --release
+//! ```
+//!
+//! ```python
+//! import numpy as np
+//! from fid_rs import Fid
+//!
+//! fid = Fid(np.array([False, True, False, False, True]))
+//! assert fid.rank(4) == 2
+//! assert list(fid.rank(np.array([0, 1, 4]))) == [0, 1, 2]
+//! assert fid.select1(1) == 4
+//! assert fid[1]
+//! ```
+
+use crate::{BitOrder, Fid};
+use numpy::{AllowTypeChange, PyArray1, PyArrayLike1, PyReadonlyArray1};
+use pyo3::exceptions::{PyDeprecationWarning, PyIndexError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyType};
+use pyo3::IntoPyObjectExt;
+
+/// Python class `fid_rs.Fid`.
+///
+/// Constructed from:
+///
+/// - 1-D NumPy `bool` array, one element per bit.
+/// - 1-D NumPy `uint8` array or `bytes` of packed bits (MSB-first, as `numpy.packbits()` returns).
+///   `bit_len` defaults to _8 * number of bytes_.
+#[pyclass(name = "Fid", module = "fid_rs", frozen)]
+pub struct PyFid {
+    fid: Fid,
+}
+
+/// Either a scalar index or a NumPy-compatible array of indices.
+#[derive(FromPyObject)]
+enum Indices<'py> {
+    Scalar(i64),
+    Array(PyArrayLike1<'py, i64, AllowTypeChange>),
+}
+
+impl PyFid {
+    fn index(&self, i: i64, bound: u64) -> PyResult<u64> {
+        if i < 0 || i as u64 >= bound {
+            Err(PyIndexError::new_err(format!(
+                "index {} is out of [0, {})",
+                i, bound
+            )))
+        } else {
+            Ok(i as u64)
+        }
+    }
+
+    fn map_scalar_or_array<'py, T, F>(
+        &self,
+        py: Python<'py>,
+        indices: Indices<'py>,
+        bound: u64,
+        f: F,
+    ) -> PyResult<Bound<'py, PyAny>>
+    where
+        T: numpy::Element + IntoPyObject<'py>,
+        F: Fn(u64) -> T,
+    {
+        match indices {
+            Indices::Scalar(i) => f(self.index(i, bound)?).into_bound_py_any(py),
+            Indices::Array(arr) => {
+                let out = arr
+                    .as_array()
+                    .iter()
+                    .map(|&i| self.index(i, bound).map(&f))
+                    .collect::<PyResult<Vec<T>>>()?;
+                Ok(PyArray1::from_vec(py, out).into_any())
+            }
+        }
+    }
+
+    /// `ks` must be in _[0, `bound`)_.
+    fn select_impl<'py, F>(
+        &self,
+        py: Python<'py>,
+        ks: Indices<'py>,
+        bound: u64,
+        select: F,
+    ) -> PyResult<Bound<'py, PyAny>>
+    where
+        F: Fn(u64) -> Option<u64>,
+    {
+        let k = |k: i64| self.index(k, bound);
+        match ks {
+            Indices::Scalar(i) => select(k(i)?).into_bound_py_any(py),
+            // Not found is represented as -1 in an array.
+            Indices::Array(arr) => {
+                let out = arr
+                    .as_array()
+                    .iter()
+                    .map(|&i| k(i).map(|k| select(k).map_or(-1, |i| i as i64)))
+                    .collect::<PyResult<Vec<i64>>>()?;
+                Ok(PyArray1::from_vec(py, out).into_any())
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl PyFid {
+    #[new]
+    #[pyo3(signature = (bits, bit_len=None))]
+    fn new(bits: &Bound<'_, PyAny>, bit_len: Option<u64>) -> PyResult<Self> {
+        let bytes: Vec<u8> = if let Ok(b) = bits.cast::<PyBytes>() {
+            b.as_bytes().to_vec()
+        } else if let Ok(arr) = bits.extract::<PyReadonlyArray1<'_, u8>>() {
+            arr.as_array().iter().copied().collect()
+        } else if let Ok(bools) = bits.extract::<PyReadonlyArray1<'_, bool>>() {
+            if bit_len.is_some() {
+                return Err(PyValueError::new_err(
+                    "bit_len must not be given for bool array",
+                ));
+            }
+            let bools: Vec<bool> = bools.as_array().iter().copied().collect();
+            if bools.is_empty() {
+                return Err(PyValueError::new_err("bits must not be empty"));
+            }
+            return Ok(Self {
+                fid: Fid::from(&bools[..]),
+            });
+        } else {
+            return Err(PyTypeError::new_err(
+                "bits must be bytes, or 1-D numpy array of uint8 or bool",
+            ));
+        };
+
+        let bit_len = bit_len.unwrap_or(bytes.len() as u64 * 8);
+        if bit_len == 0 || bit_len.div_ceil(8) != bytes.len() as u64 {
+            return Err(PyValueError::new_err(format!(
+                "bit_len (= {}) must be in [1, 8 * len(bits)] and use the last byte",
+                bit_len
+            )));
+        }
+        Ok(Self {
+            fid: Fid::from_raw_parts(bytes, bit_len, BitOrder::MsbFirst),
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.fid.len() as usize
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Fid(len={})", self.fid.len())
+    }
+
+    /// `fid[i]`, where `i` is an integer or an array of integers.
+    fn __getitem__<'py>(&self, py: Python<'py>, i: Indices<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.map_scalar_or_array(py, i, self.fid.len(), |i| self.fid[i])
+    }
+
+    /// Number of 1s in `[0, i]`. `i` may be an array.
+    fn rank<'py>(&self, py: Python<'py>, i: Indices<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.map_scalar_or_array(py, i, self.fid.len(), |i| self.fid.rank(i))
+    }
+
+    /// Number of 0s in `[0, i]`. `i` may be an array.
+    fn rank0<'py>(&self, py: Python<'py>, i: Indices<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.map_scalar_or_array(py, i, self.fid.len(), |i| self.fid.rank0(i))
+    }
+
+    /// Same as `Fid::select1()`. Returns `None` (or `-1` in an array) when not found.
+    fn select1<'py>(&self, py: Python<'py>, k: Indices<'py>) -> PyResult<Bound<'py, PyAny>> {
+        // `k` has no upper bound: too large `k` is just not found.
+        self.select_impl(py, k, u64::MAX, |k| self.fid.select1(k))
+    }
+
+    /// Same as `Fid::select0()`. Returns `None` (or `-1` in an array) when not found.
+    fn select0<'py>(&self, py: Python<'py>, k: Indices<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.select_impl(py, k, u64::MAX, |k| self.fid.select0(k))
+    }
+
+    /// Same as deprecated `Fid::select()`, which takes 1-origin `num` in _[0, len]_.
+    /// Emits `DeprecationWarning`; use `select1(num - 1)` instead.
+    fn select<'py>(&self, py: Python<'py>, num: Indices<'py>) -> PyResult<Bound<'py, PyAny>> {
+        PyErr::warn(
+            py,
+            &py.get_type::<PyDeprecationWarning>(),
+            pyo3::ffi::c_str!(
+                "Fid.select() is deprecated; use Fid.select1(), which takes 0-origin k"
+            ),
+            1,
+        )?;
+        #[allow(deprecated)]
+        self.select_impl(py, num, self.fid.len() + 1, |num| self.fid.select(num))
+    }
+
+    /// Serializes into bytes, which `Fid.deserialize()` restores.
+    fn serialize<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let bytes =
+            bincode::serialize(&self.fid).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    #[classmethod]
+    fn deserialize(_cls: &Bound<'_, PyType>, bytes: &[u8]) -> PyResult<Self> {
+        let fid = bincode::deserialize(bytes).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { fid })
+    }
+
+    /// Pickle support via `Fid.deserialize(fid.serialize())`.
+    fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, (Bound<'py, PyBytes>,))> {
+        let py = slf.py();
+        let deserialize = slf.get_type().getattr("deserialize")?;
+        Ok((deserialize, (slf.get().serialize(py)?,)))
+    }
+}
+
+/// Python module `fid_rs`.
+#[pymodule]
+fn fid_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyFid>()?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! WebAssembly bindings of [Fid](../fid/struct.Fid.html).
+//!
+//! Enabled by `wasm` feature. Build an npm package with [wasm-pack](https://rustwasm.github.io/wasm-pack/):
+//!
+//! ```text
+//! $ wasm-pack build --target web -- --features wasm
+//! $ wasm-pack test --node -- --features wasm
+//! ```
+//!
+//! Positions and counts are `u64`, which are `BigInt` in JS.
+//!
+//! ```js
+//! import { Fid } from "fid-rs";
+//!
+//! const fid = new Fid(new Uint8Array([0b0100_1000]), 5n);
+//! fid.rank(4n);    // => 2n
+//! fid.select1(1n); // => 4n
+//! fid.get(1n);     // => true
+//! const restored = Fid.fromBytes(fid.toBytes());
+//! ```
+
+use crate::{BitOrder, Fid};
+use wasm_bindgen::prelude::*;
+
+/// JS class `Fid`.
+#[wasm_bindgen(js_name = Fid)]
+pub struct WasmFid {
+    fid: Fid,
+}
+
+impl WasmFid {
+    fn check_index(&self, i: u64) -> Result<(), JsError> {
+        if i < self.fid.len() {
+            Ok(())
+        } else {
+            Err(JsError::new(&format!(
+                "index {} is out of [0, {})",
+                i,
+                self.fid.len()
+            )))
+        }
+    }
+}
+
+#[wasm_bindgen(js_class = Fid)]
+impl WasmFid {
+    /// Builds a `Fid` from packed bits (MSB-first) and its bit length.
+    ///
+    /// `bytes.length` must be _ceil(`bitLen` / 8)_.
+    #[wasm_bindgen(constructor)]
+    pub fn new(bytes: &[u8], bit_len: u64) -> Result<WasmFid, JsError> {
+        if bit_len == 0 || bit_len.div_ceil(8) != bytes.len() as u64 {
+            return Err(JsError::new(&format!(
+                "bitLen (= {}) must be in [1, 8 * bytes.length] and use the last byte",
+                bit_len
+            )));
+        }
+        Ok(WasmFid {
+            fid: Fid::from_raw_parts(bytes.to_vec(), bit_len, BitOrder::MsbFirst),
+        })
+    }
+
+    /// Bit length.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> u64 {
+        self.fid.len()
+    }
+
+    /// `i`-th bit.
+    pub fn get(&self, i: u64) -> Result<bool, JsError> {
+        self.check_index(i)?;
+        Ok(self.fid[i])
+    }
+
+    /// Number of 1s in _[0, `i`]_.
+    pub fn rank(&self, i: u64) -> Result<u64, JsError> {
+        self.check_index(i)?;
+        Ok(self.fid.rank(i))
+    }
+
+    /// Number of 0s in _[0, `i`]_.
+    pub fn rank0(&self, i: u64) -> Result<u64, JsError> {
+        self.check_index(i)?;
+        Ok(self.fid.rank0(i))
+    }
+
+    /// Same as `Fid::select1()`. Returns `undefined` when not found.
+    pub fn select1(&self, k: u64) -> Option<u64> {
+        self.fid.select1(k)
+    }
+
+    /// Same as `Fid::select0()`. Returns `undefined` when not found.
+    pub fn select0(&self, k: u64) -> Option<u64> {
+        self.fid.select0(k)
+    }
+
+    /// Same as deprecated `Fid::select()`, which takes 1-origin `num` in _[0, length]_.
+    /// Returns `undefined` when not found.
+    ///
+    /// @deprecated Use `select1(k)`, which takes 0-origin `k`.
+    pub fn select(&self, num: u64) -> Result<Option<u64>, JsError> {
+        if num > self.fid.len() {
+            return Err(JsError::new(&format!(
+                "num {} is out of [0, {}]",
+                num,
+                self.fid.len()
+            )));
+        }
+        #[allow(deprecated)]
+        Ok(self.fid.select(num))
+    }
+
+    /// Serializes into bytes: the bit length as 8-byte little endian integer, followed by packed bits.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.fid.to_length_prefixed_bytes()
+    }
+
+    /// Inverse of `toBytes()`.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmFid, JsError> {
+        Fid::from_length_prefixed_bytes(bytes)
+            .map(|fid| WasmFid { fid })
+            .ok_or_else(|| JsError::new("bytes is not serialized by Fid.toBytes()"))
+    }
+}
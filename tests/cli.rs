@@ -0,0 +1,133 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn fid(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_fid"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Temporary directory for a test, removed on drop.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(test_name: &str) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("fid-rs-cli-{}-{}", std::process::id(), test_name));
+        std::fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn stdout_of(output: Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn build_from_each_format_and_query() {
+    let tmp = TempDir::new("build_from_each_format_and_query");
+    let bytes_input = tmp.path("input.bin");
+    std::fs::write(&bytes_input, [0b0100_1000]).unwrap();
+    let bytes_input = bytes_input.to_str().unwrap();
+
+    let inputs: [(&[&str], &str); 3] = [
+        (&["--format", "bits", "-"], "0100\n_1\n"),
+        (&["--format", "bytes", "--len", "5", bytes_input], ""),
+        (&["--format", "positions", "--len", "5", "-"], "1\n4\n"),
+    ];
+    for (i, (args, stdin)) in inputs.iter().enumerate() {
+        let out = tmp.path(&format!("{}.fid", i));
+        let out = out.to_str().unwrap();
+        let mut build_args = vec!["build"];
+        build_args.extend_from_slice(args);
+        build_args.push(out);
+        stdout_of(fid(&build_args, stdin));
+
+        assert_eq!(
+            stdout_of(fid(&["query", "get", out], "0\n1\n4\n")),
+            "0\n1\n1\n"
+        );
+        assert_eq!(
+            stdout_of(fid(&["query", "rank", out], "0\n3\n4\n")),
+            "0\n1\n2\n"
+        );
+        assert_eq!(stdout_of(fid(&["query", "rank0", out], "4\n")), "3\n");
+        assert_eq!(
+            stdout_of(fid(&["query", "select1", out], "1\n2\n")),
+            "4\nnone\n"
+        );
+        assert_eq!(stdout_of(fid(&["query", "select0", out], "2\n")), "3\n");
+        assert_eq!(
+            stdout_of(fid(&["query", "select", out], "0\n2\n3\n")),
+            "0\n4\nnone\n"
+        );
+
+        let stats = stdout_of(fid(&["stats", out], ""));
+        assert!(stats.contains("length:          5\n"), "{}", stats);
+        assert!(stats.contains("popcount:        2\n"), "{}", stats);
+        assert!(stats.contains("density:         0.400000\n"), "{}", stats);
+    }
+}
+
+#[test]
+fn invalid_input() {
+    let tmp = TempDir::new("invalid_input");
+    let out = tmp.path("invalid.fid");
+    let out = out.to_str().unwrap();
+
+    assert!(!fid(&["build", "-", out], "01x").status.success());
+    assert!(!fid(&["build", "--format", "positions", "-", out], "1\n")
+        .status
+        .success());
+    assert!(!fid(
+        &["build", "--format", "positions", "--len", "2", "-", out],
+        "2\n"
+    )
+    .status
+    .success());
+
+    assert!(!fid(&["build", "--len", "3", "-", out], "01\n")
+        .status
+        .success());
+    assert!(!fid(&["build", "--format", "bytes", "-", out], "")
+        .status
+        .success());
+    assert!(
+        !fid(&["build", "--format", "bytes", "--len", "9", "-", out], "a")
+            .status
+            .success()
+    );
+
+    stdout_of(fid(&["build", "-", out], "01"));
+    assert!(!fid(&["query", "rank", out], "2\n").status.success());
+    assert!(!fid(&["query", "select", out], "3\n").status.success());
+}
@@ -0,0 +1,166 @@
+//! Property-based tests comparing every public query of `Fid` with a naive `Vec<bool>` oracle.
+#![cfg(not(target_arch = "wasm32"))]
+
+use fid_rs::{BitOrder, Fid};
+use proptest::prelude::*;
+
+/// Naive bit vector answering queries by scanning.
+struct Oracle {
+    bits: Vec<bool>,
+
+    /// `ranks[i]` = number of '1's in _[0, i]_.
+    ranks: Vec<u64>,
+
+    /// Positions of '1's.
+    ones: Vec<u64>,
+
+    /// Positions of '0's.
+    zeros: Vec<u64>,
+}
+
+impl Oracle {
+    fn new(bits: Vec<bool>) -> Self {
+        let ranks = bits
+            .iter()
+            .scan(0, |rank, &bit| {
+                *rank += bit as u64;
+                Some(*rank)
+            })
+            .collect();
+        let (ones, zeros): (Vec<u64>, Vec<u64>) =
+            (0..bits.len() as u64).partition(|&i| bits[i as usize]);
+        Oracle {
+            bits,
+            ranks,
+            ones,
+            zeros,
+        }
+    }
+
+    fn get(&self, i: u64) -> bool {
+        self.bits[i as usize]
+    }
+
+    fn rank(&self, i: u64) -> u64 {
+        self.ranks[i as usize]
+    }
+
+    fn rank0(&self, i: u64) -> u64 {
+        i + 1 - self.rank(i)
+    }
+
+    fn select1(&self, k: u64) -> Option<u64> {
+        self.ones.get(k as usize).copied()
+    }
+
+    fn select0(&self, k: u64) -> Option<u64> {
+        self.zeros.get(k as usize).copied()
+    }
+}
+
+/// Same as `Chunks::calc_chunk_size()`: _(log N)^2_.
+fn chunk_size(n: u64) -> u64 {
+    let lg2 = n.checked_ilog2().unwrap_or(0) as u64;
+    (lg2 * lg2).max(1)
+}
+
+/// Same as `Chunks::calc_block_size()`: _(log N) / 2_.
+fn block_size(n: u64) -> u64 {
+    let lg2 = n.checked_ilog2().unwrap_or(0) as u64;
+    (lg2 / 2).max(1)
+}
+
+/// Lengths just around a power of 2 (where chunk and block sizes change),
+/// and around multiples of chunk and block sizes.
+fn boundary_len() -> impl Strategy<Value = u64> {
+    (1u32..=13, 1u64..=4, -1i64..=1, prop::bool::ANY).prop_map(|(lg2, k, delta, by_chunk)| {
+        let n = 1u64 << lg2;
+        let unit = if by_chunk {
+            chunk_size(n)
+        } else {
+            block_size(n)
+        };
+        let base = if k == 1 { n } else { unit * k };
+        (base as i64 + delta).max(1) as u64
+    })
+}
+
+fn bit_vec() -> impl Strategy<Value = Vec<bool>> {
+    let len = prop_oneof![1u64..=300, boundary_len(), 300u64..=5000];
+    let density = prop_oneof![Just(0.0), Just(1.0), 0.0f64..=1.0];
+    (len, density).prop_flat_map(|(len, density)| {
+        prop::collection::vec(prop::bool::weighted(density), len as usize)
+    })
+}
+
+proptest! {
+    #[test]
+    fn index_and_iter(bits in bit_vec()) {
+        let fid = Fid::from(&bits[..]);
+        prop_assert_eq!(fid.iter().collect::<Vec<bool>>(), bits.clone());
+
+        let oracle = Oracle::new(bits);
+        prop_assert_eq!(fid.len(), oracle.bits.len() as u64);
+        for i in 0..fid.len() {
+            prop_assert_eq!(fid[i], oracle.get(i), "i = {}", i);
+        }
+    }
+
+    #[test]
+    fn rank_and_rank0(bits in bit_vec()) {
+        let fid = Fid::from(&bits[..]);
+        let oracle = Oracle::new(bits);
+        for i in 0..fid.len() {
+            prop_assert_eq!(fid.rank(i), oracle.rank(i), "i = {}", i);
+            prop_assert_eq!(fid.rank0(i), oracle.rank0(i), "i = {}", i);
+        }
+    }
+
+    #[test]
+    fn select1_and_select0(bits in bit_vec()) {
+        let fid = Fid::from(&bits[..]);
+        let oracle = Oracle::new(bits);
+        for k in 0..=fid.len() {
+            prop_assert_eq!(fid.select1(k), oracle.select1(k), "k = {}", k);
+            prop_assert_eq!(fid.select0(k), oracle.select0(k), "k = {}", k);
+        }
+        for i in 0..fid.len() {
+            let (rank, select) = if fid[i] {
+                (fid.rank(i), fid.select1(fid.rank(i) - 1))
+            } else {
+                (fid.rank0(i), fid.select0(fid.rank0(i) - 1))
+            };
+            prop_assert_eq!(select, Some(i), "i = {}, rank = {}", i, rank);
+        }
+    }
+
+    #[test]
+    fn lsb_first_same_as_msb_first(bits in bit_vec()) {
+        let msb = Fid::from(&bits[..]);
+        let mut lsb_bytes = vec![0u8; bits.len().div_ceil(8)];
+        for (i, &bit) in bits.iter().enumerate() {
+            lsb_bytes[i / 8] |= (bit as u8) << (i % 8);
+        }
+        let lsb = Fid::from_raw_parts(lsb_bytes, bits.len() as u64, BitOrder::LsbFirst);
+        prop_assert_eq!(&lsb, &msb);
+        for i in 0..msb.len() {
+            prop_assert_eq!(lsb.rank(i), msb.rank(i), "i = {}", i);
+            prop_assert_eq!(lsb.select1(i), msb.select1(i), "i = {}", i);
+            prop_assert_eq!(lsb.select0(i), msb.select0(i), "i = {}", i);
+        }
+    }
+
+    #[test]
+    fn from_str_same_as_from_slice(bits in bit_vec()) {
+        let s: String = bits.iter().map(|&b| if b { '1' } else { '0' }).collect();
+        let from_str = Fid::from(s.as_str());
+        prop_assert!(from_str.iter().eq(bits.iter().copied()));
+    }
+
+    #[test]
+    fn hex_and_base64_round_trip(bits in bit_vec()) {
+        let fid = Fid::from(&bits[..]);
+        prop_assert_eq!(Fid::from_hex(&fid.to_hex()), Ok(fid.clone()));
+        prop_assert_eq!(Fid::from_base64(&fid.to_base64()), Ok(fid));
+    }
+}
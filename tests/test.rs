@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison, clippy::needless_range_loop)]
+
 use fid_rs::Fid;
 
 #[test]
@@ -37,38 +39,20 @@ fn fuzzing_test() {
         rank0
     }
 
-    fn select_from_bit_string(s: &str, num: u64) -> Option<u64> {
-        if num == 0 {
-            return Some(0);
-        }
-
-        let mut cnt: u64 = 0;
-        for (i, ch) in s.chars().enumerate() {
-            if ch == '1' {
-                cnt += 1;
-            }
-            if cnt == num {
-                return Some(i as u64);
-            }
-        }
-        None
+    fn select1_from_bit_string(s: &str, k: u64) -> Option<u64> {
+        s.chars()
+            .enumerate()
+            .filter(|&(_, ch)| ch == '1')
+            .nth(k as usize)
+            .map(|(i, _)| i as u64)
     }
 
-    fn select0_from_bit_string(s: &str, num: u64) -> Option<u64> {
-        if num == 0 {
-            return Some(0);
-        }
-
-        let mut cnt: u64 = 0;
-        for (i, ch) in s.chars().enumerate() {
-            if ch == '0' {
-                cnt += 1;
-            }
-            if cnt == num {
-                return Some(i as u64);
-            }
-        }
-        None
+    fn select0_from_bit_string(s: &str, k: u64) -> Option<u64> {
+        s.chars()
+            .enumerate()
+            .filter(|&(_, ch)| ch == '0')
+            .nth(k as usize)
+            .map(|(i, _)| i as u64)
     }
 
     for _ in 0..samples {
@@ -100,16 +84,16 @@ fn fuzzing_test() {
                 rank_from_bit_string(s, i as u64)
             );
 
-            let num = i as u64;
-            eprintln!("select(): bit vec = \"{}\", num = {}, ", s, num);
+            let k = i as u64;
+            eprintln!("select1(): bit vec = \"{}\", k = {}, ", s, k);
             assert_eq!(
-                fid.select(num),
-                select_from_bit_string(s, num),
-                "bit vec = \"{}\", num={}, Fid::select()={:?}, select_from_bit_string={:?}",
+                fid.select1(k),
+                select1_from_bit_string(s, k),
+                "bit vec = \"{}\", k={}, Fid::select1()={:?}, select1_from_bit_string={:?}",
                 s,
-                num,
-                fid.select(num),
-                select_from_bit_string(s, num)
+                k,
+                fid.select1(k),
+                select1_from_bit_string(s, k)
             );
 
             eprintln!("rank0(): bit vec = \"{}\", i = {}, ", s, i);
@@ -123,16 +107,16 @@ fn fuzzing_test() {
                 rank0_from_bit_string(s, i as u64)
             );
 
-            let num = i as u64;
-            eprintln!("select0(): bit vec = \"{}\", num = {}, ", s, num);
+            let k = i as u64;
+            eprintln!("select0(): bit vec = \"{}\", k = {}, ", s, k);
             assert_eq!(
-                fid.select0(num),
-                select0_from_bit_string(s, num),
-                "bit vec = \"{}\", num={}, Fid::select0()={:?}, select0_from_bit_string={:?}",
+                fid.select0(k),
+                select0_from_bit_string(s, k),
+                "bit vec = \"{}\", k={}, Fid::select0()={:?}, select0_from_bit_string={:?}",
                 s,
-                num,
-                fid.select0(num),
-                select0_from_bit_string(s, num)
+                k,
+                fid.select0(k),
+                select0_from_bit_string(s, k)
             );
         }
     }
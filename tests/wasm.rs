@@ -0,0 +1,47 @@
+//! Run with `wasm-pack test --node -- --features wasm`.
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use fid_rs::wasm::WasmFid;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn query() {
+    let fid = WasmFid::new(&[0b0100_1000], 5).unwrap();
+    assert_eq!(fid.length(), 5);
+    assert!(fid.get(1).unwrap());
+    assert!(!fid.get(2).unwrap());
+    assert_eq!(fid.rank(4).unwrap(), 2);
+    assert_eq!(fid.rank0(4).unwrap(), 3);
+    assert_eq!(fid.select1(1), Some(4));
+    assert_eq!(fid.select1(2), None);
+    assert_eq!(fid.select0(2), Some(3));
+    assert!(fid.get(5).is_err());
+    assert!(fid.rank(5).is_err());
+}
+
+#[wasm_bindgen_test]
+fn deprecated_select() {
+    let fid = WasmFid::new(&[0b0100_1000], 5).unwrap();
+    assert_eq!(fid.select(0).unwrap(), Some(0));
+    assert_eq!(fid.select(2).unwrap(), Some(4));
+    assert_eq!(fid.select(3).unwrap(), None);
+    assert!(fid.select(6).is_err());
+}
+
+#[wasm_bindgen_test]
+fn invalid_bit_len() {
+    assert!(WasmFid::new(&[0b0100_1000], 0).is_err());
+    assert!(WasmFid::new(&[0b0100_1000], 9).is_err());
+    assert!(WasmFid::new(&[0b0100_1000, 0], 8).is_err());
+}
+
+#[wasm_bindgen_test]
+fn bytes_round_trip() {
+    let fid = WasmFid::new(&[0b1010_1010, 0b1111_1111], 11).unwrap();
+    let restored = WasmFid::from_bytes(&fid.to_bytes()).unwrap();
+    assert_eq!(restored.length(), 11);
+    for i in 0..11 {
+        assert_eq!(restored.get(i).unwrap(), fid.get(i).unwrap());
+    }
+    assert!(WasmFid::from_bytes(&[11, 0, 0]).is_err());
+}